@@ -0,0 +1,95 @@
+extern crate regex;
+extern crate two_timer;
+
+use regex::Regex;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::process;
+use two_timer::log_filter::{LogFilter, Timestamp};
+use two_timer::{parse_range, Config};
+
+const USAGE: &str = "\
+usage: log-filter [options] <expression> [file...]
+
+Prints the lines of the given log files, or of STDIN if there are none, whose
+timestamps fall in the range described by the time expression. Lines without
+timestamps are kept if the line before them is kept.
+
+options:
+  --format <format>          a strftime format for timestamps at the start of each
+                             line; default '%Y-%m-%d %H:%M:%S'
+  --pattern <regex>          a regular expression locating the timestamp anywhere in
+                             the line; its first group, or the whole match, is parsed
+                             as a time expression
+  --sorted                   the log is sorted, so stop after the end of the range
+  --now <expression>         interpret expressions relative to this moment
+  --week-starts-sunday       regard Sunday as the first day of the week
+  --future                   assume ambiguous expressions refer to the future
+  -h, --help                 print this message
+";
+
+fn main() {
+    let mut config = Config::new();
+    let mut timestamp = Timestamp::Format("%Y-%m-%d %H:%M:%S".to_string());
+    let mut sorted = false;
+    let mut positional = vec![];
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return;
+            }
+            "--format" => timestamp = Timestamp::Format(value(&arg, args.next())),
+            "--pattern" => match Regex::new(&value(&arg, args.next())) {
+                Ok(rx) => timestamp = Timestamp::Pattern(rx),
+                Err(e) => fail(&e.to_string()),
+            },
+            "--sorted" => sorted = true,
+            "--now" => match parse_range(&value(&arg, args.next()), Some(config.clone())) {
+                Ok(range) => config = config.now(range.start()),
+                Err(e) => fail(e.msg()),
+            },
+            "--week-starts-sunday" => config = config.monday_starts_week(false),
+            "--future" => config = config.default_to_past(false),
+            _ => {
+                if arg.starts_with("--") {
+                    fail(&format!("unknown option: {}", arg));
+                }
+                positional.push(arg);
+            }
+        }
+    }
+    if positional.is_empty() {
+        fail("a time expression is required");
+    }
+    let expression = positional.remove(0);
+    let filter = match LogFilter::new(&expression, timestamp, Some(config)) {
+        Ok(filter) => filter.sorted(sorted),
+        Err(e) => fail(e.msg()),
+    };
+    let stdout = io::stdout();
+    let result = if positional.is_empty() {
+        filter.filter(io::stdin().lock(), stdout.lock())
+    } else {
+        positional.iter().try_fold(0, |count, path| {
+            let file = File::open(path)?;
+            Ok(count + filter.filter(BufReader::new(file), stdout.lock())?)
+        })
+    };
+    if let Err(e) = result {
+        if e.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn value(option: &str, value: Option<String>) -> String {
+    value.unwrap_or_else(|| fail(&format!("{} requires a value", option)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    process::exit(2)
+}
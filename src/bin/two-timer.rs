@@ -0,0 +1,139 @@
+extern crate chrono;
+extern crate serde_json;
+extern crate two_timer;
+
+use chrono::NaiveDateTime;
+use std::io::{self, BufRead};
+use std::process;
+use two_timer::{parse_range, Config, TimeRange};
+
+const USAGE: &str = "\
+usage: two-timer [options] [expression...]
+
+Parses an English time expression and prints the first and last moments of the
+range it describes. If no expression is given, expressions are read from STDIN,
+one per line.
+
+options:
+  --now <expression>                 interpret expressions relative to this moment
+  --week-starts-sunday               regard Sunday as the first day of the week
+  --future                           assume ambiguous expressions refer to the future
+  --pay-period-start <expression>    the first day of some pay period
+  --pay-period-length <days>         the length of a pay period in days; default 7
+  --format <plain|iso|json>          the output format; default plain
+  -h, --help                         print this message
+";
+
+#[derive(Clone, Copy)]
+enum Format {
+    Plain,
+    Iso,
+    Json,
+}
+
+fn main() {
+    let mut config = Config::new();
+    let mut format = Format::Plain;
+    let mut words = vec![];
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                return;
+            }
+            "--now" => {
+                let now = moment(&value(&arg, args.next()), &config);
+                config = config.now(now);
+            }
+            "--week-starts-sunday" => config = config.monday_starts_week(false),
+            "--future" => config = config.default_to_past(false),
+            "--pay-period-start" => {
+                let start = moment(&value(&arg, args.next()), &config);
+                config = config.pay_period_start(Some(start.date()));
+            }
+            "--pay-period-length" => match value(&arg, args.next()).parse::<u32>() {
+                Ok(n) if n > 0 => config = config.pay_period_length(n),
+                _ => fail("--pay-period-length requires a positive number of days"),
+            },
+            "--format" => {
+                format = match value(&arg, args.next()).as_ref() {
+                    "plain" => Format::Plain,
+                    "iso" => Format::Iso,
+                    "json" => Format::Json,
+                    f => fail(&format!("unknown format: {}", f)),
+                }
+            }
+            _ => {
+                if arg.starts_with("--") {
+                    fail(&format!("unknown option: {}", arg));
+                }
+                words.push(arg);
+            }
+        }
+    }
+    let mut ok = true;
+    if words.is_empty() {
+        for line in io::stdin().lock().lines() {
+            let line = line.unwrap_or_else(|e| fail(&e.to_string()));
+            let line = line.trim();
+            if !line.is_empty() {
+                ok = show(line, &config, format) && ok;
+            }
+        }
+    } else {
+        ok = show(&words.join(" "), &config, format);
+    }
+    if !ok {
+        process::exit(1);
+    }
+}
+
+// parse an expression and print the result, returning whether the expression was parsable
+fn show(expression: &str, config: &Config, format: Format) -> bool {
+    match parse_range(expression, Some(config.clone())) {
+        Ok(range) => {
+            println!("{}", render(expression, &range, format));
+            true
+        }
+        Err(e) => {
+            eprintln!("{}: {}", expression, e.msg());
+            false
+        }
+    }
+}
+
+fn render(expression: &str, range: &TimeRange, format: Format) -> String {
+    match format {
+        Format::Plain => format!("{} --- {}", range.start(), range.end()),
+        Format::Iso => format!("{}/{}", iso(range.start()), iso(range.end())),
+        Format::Json => serde_json::json!({
+            "expression": expression,
+            "start": iso(range.start()),
+            "end": iso(range.end()),
+            "range": range.is_range(),
+        })
+        .to_string(),
+    }
+}
+
+fn iso(t: NaiveDateTime) -> String {
+    t.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+}
+
+// the first moment of the range described by an option value
+fn moment(expression: &str, config: &Config) -> NaiveDateTime {
+    match parse_range(expression, Some(config.clone())) {
+        Ok(range) => range.start(),
+        Err(e) => fail(e.msg()),
+    }
+}
+
+fn value(option: &str, value: Option<String>) -> String {
+    value.unwrap_or_else(|| fail(&format!("{} requires a value", option)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    process::exit(2)
+}
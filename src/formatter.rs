@@ -0,0 +1,376 @@
+/*!
+
+Rendering time ranges back into English.
+
+The `describe` function is the inverse of `parse`. Given the first and last moments of a range
+it produces the most natural phrase it can which `parse`, with the same configuration,
+interprets as that very range. It works the phrase out from the periods the range begins and
+ends on -- "today", "last week", "May 1969", "the past 2 hours", "3 PM to 5 PM tomorrow" --
+and confirms it with a single parse, falling back to the least natural phrase, "1969-05-06
+12:03:05 to 1969-05-06 12:04:17", if the confirmation fails.
+
+# Example
+
+```rust
+# extern crate two_timer;
+# use two_timer::formatter::describe;
+# use two_timer::{parse, Config};
+# use chrono::NaiveDate;
+let now = NaiveDate::from_ymd_opt(1969, 5, 6).unwrap().and_hms_opt(12, 3, 5).unwrap();
+let config = Config::new().now(now);
+let (start, end, _) = parse("last week", Some(config.clone())).unwrap();
+assert_eq!("last week", describe(start, end, Some(config.clone())));
+let (start, end, _) = parse("5/6/69", Some(config.clone())).unwrap();
+assert_eq!("today", describe(start, end, Some(config.clone())));
+let (start, end, _) = parse("5/6/1968", Some(config.clone())).unwrap();
+assert_eq!("May 6, 1968", describe(start, end, Some(config)));
+```
+
+*/
+
+use crate::{first_moment, last_moment, moment_to_period, parse, shift, Config, Period};
+use chrono::naive::{NaiveDate, NaiveDateTime};
+use chrono::{Datelike, Duration, Timelike};
+
+/// Describes a time range in English relative to the configuration, so that `parse` given
+/// the phrase and the same configuration returns the same range. If the range cannot be
+/// expressed in the grammar, as when it begins or ends at a fraction of a second, the
+/// description is the closest approximation available: the two timestamps joined by "to".
+pub fn describe(start: NaiveDateTime, end: NaiveDateTime, config: Option<Config>) -> String {
+    let config = config.unwrap_or_default();
+    if let Some(phrase) = phrase(start, end, &config) {
+        if parse(&phrase, Some(config.clone())).is_ok_and(|(s, e, _)| s == start && e == end) {
+            return phrase;
+        }
+    }
+    format!("{} to {}", precise(start), precise(end))
+}
+
+// the most natural phrase for the range, if there is one short of the timestamps
+fn phrase(start: NaiveDateTime, end: NaiveDateTime, config: &Config) -> Option<String> {
+    if start > end {
+        return None;
+    }
+    if start == first_moment() && end == last_moment() {
+        return Some("always".to_string());
+    }
+    // the periods containing these moments are not representable, and relative expressions
+    // cannot be interpreted relative to them, so the other moment must be specific or now,
+    // which begins with the current minute
+    let (now, _) = moment_to_period(config.now, &Period::Minute, config);
+    if start == first_moment() {
+        return Some(if end == now {
+            "the beginning of time to now".to_string()
+        } else {
+            format!("before {}", specific_name(end))
+        });
+    }
+    if end == last_moment() {
+        return Some(if start == now {
+            "now to the end of time".to_string()
+        } else {
+            format!("{} onward", specific_name(start))
+        });
+    }
+    // a single period
+    for period in PERIODS.iter() {
+        if moment_to_period(start, period, config) == (start, end) {
+            if let Some(name) = name(start, period, config) {
+                return Some(name);
+            }
+        }
+    }
+    if let Some(weekend) = weekend(start, end, config) {
+        return Some(weekend);
+    }
+    if let Some(window) = rolling_window(start, end, config) {
+        return Some(window);
+    }
+    if !aligned(start, &Period::Second, config) || !aligned(end, &Period::Second, config) {
+        return None;
+    }
+    // times on a single day
+    let day = start.date();
+    if let (Some(first), Some(last)) = (clock_on(start, day), clock_on(end, day)) {
+        return Some(on_day(format!("{} to {}", first, last), day, config));
+    }
+    // a span of periods
+    for period in PERIODS[..PERIODS.len() - 3].iter() {
+        if aligned(start, period, config) && aligned(end, period, config) {
+            let (last, _) = moment_to_period(end - Duration::seconds(1), period, config);
+            if let (Some(first), Some(last)) =
+                (name(start, period, config), name(last, period, config))
+            {
+                return Some(format!("{} through {}", first, last));
+            }
+        }
+    }
+    // a span between moments
+    Some(format!(
+        "{} to {}",
+        moment(start, config),
+        moment(end, config)
+    ))
+}
+
+// the periods which may be named, from the coarsest to the finest
+const PERIODS: [Period; 8] = [
+    Period::Year,
+    Period::Quarter,
+    Period::Month,
+    Period::Week,
+    Period::Day,
+    Period::Hour,
+    Period::Minute,
+    Period::Second,
+];
+
+// whether a moment is the first moment of a period
+fn aligned(t: NaiveDateTime, period: &Period, config: &Config) -> bool {
+    moment_to_period(t, period, config).0 == t
+}
+
+// the name of the period of the given type beginning at the given moment; the period is
+// named relative to now when it is near enough
+fn name(t: NaiveDateTime, period: &Period, config: &Config) -> Option<String> {
+    let unit = match period {
+        Period::Year => "year",
+        Period::Quarter => "quarter",
+        Period::Month => "month",
+        Period::Week => "week",
+        Period::Day => return Some(day(t.date(), config)),
+        Period::Hour => "hour",
+        Period::Minute => "minute",
+        _ => "second",
+    };
+    // "2 weeks ago", "3 hours from now"
+    let (now, _) = moment_to_period(config.now, period, config);
+    let n = match period {
+        Period::Year => (now.year() - t.year()) as i64,
+        Period::Quarter => months(now, t) / 3,
+        Period::Month => months(now, t),
+        _ => {
+            let (_, next) = moment_to_period(t, period, config);
+            (now - t).num_seconds() / (next - t).num_seconds()
+        }
+    };
+    let modifier = match n {
+        0 => "this",
+        1 => "last",
+        -1 => "next",
+        _ => "",
+    };
+    match period {
+        Period::Year | Period::Quarter | Period::Month | Period::Week if !modifier.is_empty() => {
+            return Some(format!("{} {}", modifier, unit))
+        }
+        Period::Year => return Some(year(t.year())),
+        Period::Quarter => return Some(format!("Q{} {}", t.month0() / 3 + 1, year(t.year()))),
+        Period::Month => return Some(format!("{} {}", month(t.month()), year(t.year()))),
+        // "2 weeks ago" is the 7 days ending 2 weeks ago, not a calendar week, so other
+        // weeks are named by their days
+        Period::Week => return None,
+        Period::Minute if n == 0 => return Some("now".to_string()),
+        Period::Second => return Some(moment(t, config)),
+        _ => (),
+    }
+    let s = if n.abs() == 1 { "" } else { "s" };
+    match n.signum() {
+        1 => Some(format!("{} {}{} ago", n, unit, s)),
+        -1 => Some(format!("{} {}{} from now", -n, unit, s)),
+        _ => None,
+    }
+}
+
+// the number of months from t2 to t1
+fn months(t1: NaiveDateTime, t2: NaiveDateTime) -> i64 {
+    (t1.year() as i64 * 12 + t1.month0() as i64) - (t2.year() as i64 * 12 + t2.month0() as i64)
+}
+
+// the name of a particular day
+fn day(d: NaiveDate, config: &Config) -> String {
+    match (d - config.now.date()).num_days() {
+        0 => "today".to_string(),
+        -1 => "yesterday".to_string(),
+        1 => "tomorrow".to_string(),
+        _ => date(d),
+    }
+}
+
+fn date(d: NaiveDate) -> String {
+    format!("{} {}, {}", month(d.month()), d.day(), year(d.year()))
+}
+
+// a phrase such as a time or pair of times placed on a particular day
+fn on_day(phrase: String, d: NaiveDate, config: &Config) -> String {
+    let day = day(d, config);
+    if day.contains(',') {
+        format!("{} on {}", phrase, day)
+    } else {
+        format!("{} {}", phrase, day)
+    }
+}
+
+// the name of a particular second
+fn moment(t: NaiveDateTime, config: &Config) -> String {
+    if t.nanosecond() > 0 {
+        return precise(t);
+    }
+    let (clock, d) = clocks(t).swap_remove(0);
+    on_day(clock, d, config)
+}
+
+// the name of the coarsest period beginning at a moment which is not interpreted relative to
+// some other moment
+fn specific_name(t: NaiveDateTime) -> String {
+    if t.nanosecond() > 0 {
+        precise(t)
+    } else if t.num_seconds_from_midnight() > 0 {
+        let (clock, d) = clocks(t).swap_remove(0);
+        format!("{} on {}", clock, date(d))
+    } else if t.day() > 1 {
+        date(t.date())
+    } else if t.month() > 1 {
+        format!("{} {}", month(t.month()), year(t.year()))
+    } else {
+        year(t.year())
+    }
+}
+
+// the name of a time of day on a particular day, if it can be named as belonging to that day
+fn clock_on(t: NaiveDateTime, d: NaiveDate) -> Option<String> {
+    clocks(t)
+        .into_iter()
+        .find(|(_, day)| *day == d)
+        .map(|(clock, _)| clock)
+}
+
+// "this weekend" or "last weekend" if the range is a weekend near now
+fn weekend(start: NaiveDateTime, end: NaiveDateTime, config: &Config) -> Option<String> {
+    if start.weekday() != chrono::Weekday::Sat
+        || !aligned(start, &Period::Day, config)
+        || end != start + Duration::days(2)
+    {
+        return None;
+    }
+    let today = config.now.date();
+    let saturday = today + Duration::days(5 - today.weekday().num_days_from_monday() as i64);
+    match (start.date() - saturday).num_weeks() {
+        0 => Some("this weekend".to_string()),
+        -1 => Some("last weekend".to_string()),
+        1 => Some("next weekend".to_string()),
+        _ => None,
+    }
+}
+
+// "the past 2 hours" or "the next 3 days" if the range is a rolling window ending or
+// beginning now
+fn rolling_window(start: NaiveDateTime, end: NaiveDateTime, config: &Config) -> Option<String> {
+    for (unit, period) in [
+        ("year", Period::Year),
+        ("month", Period::Month),
+        ("week", Period::Week),
+        ("day", Period::Day),
+        ("hour", Period::Hour),
+        ("minute", Period::Minute),
+        ("second", Period::Second),
+    ] {
+        let (d1, d2) = if config.whole_windows {
+            moment_to_period(config.now, &period, config)
+        } else {
+            (config.now, config.now)
+        };
+        let n = match period {
+            Period::Year => months(end, start) / 12,
+            Period::Month => months(end, start),
+            _ => match shift(start, 1, unit) {
+                Ok(t) => (end - start).num_seconds() / (t - start).num_seconds(),
+                Err(_) => continue,
+            },
+        };
+        // beyond this a phrase naming the ends is clearer
+        if !(1..100).contains(&n) {
+            continue;
+        }
+        let direction = if end == d1 && shift(d1, -n, unit).is_ok_and(|t| t == start) {
+            "past"
+        } else if start == d2 && shift(d2, n, unit).is_ok_and(|t| t == end) {
+            "next"
+        } else {
+            continue;
+        };
+        return Some(if n == 1 {
+            format!("the {} {}", direction, unit)
+        } else {
+            format!("the {} {} {}s", direction, n, unit)
+        });
+    }
+    None
+}
+
+// the ways of naming a time of day, each with the day it belongs to; the grammar treats
+// midnight as the end of a day, hour 0 as hour 24, and 12 PM as 0:00, so these need care
+fn clocks(t: NaiveDateTime) -> Vec<(String, NaiveDate)> {
+    let (pm, hour) = t.hour12();
+    let suffix = if pm { "PM" } else { "AM" };
+    let exact = t.minute() == 0 && t.second() == 0;
+    let mut clocks = vec![];
+    match t.hour() {
+        0 if exact => clocks.push(("midnight".to_string(), t.date().pred_opt().unwrap())),
+        12 if exact => clocks.push(("noon".to_string(), t.date())),
+        _ => (),
+    }
+    let clock = if t.hour() == 0 || t.hour() == 12 {
+        let hour = if t.hour() == 0 { 24 } else { 12 };
+        if t.second() > 0 {
+            format!("{}:{:02}:{:02}", hour, t.minute(), t.second())
+        } else {
+            format!("{}:{:02}", hour, t.minute())
+        }
+    } else if t.second() > 0 {
+        format!("{}:{:02}:{:02} {}", hour, t.minute(), t.second(), suffix)
+    } else if t.minute() > 0 {
+        format!("{}:{:02} {}", hour, t.minute(), suffix)
+    } else {
+        format!("{} {}", hour, suffix)
+    };
+    clocks.push((clock, t.date()));
+    clocks
+}
+
+fn precise(t: NaiveDateTime) -> String {
+    let s = t.format("%Y-%m-%d %H:%M:%S");
+    if t.nanosecond() > 0 {
+        format!("{}{}", s, t.format("%.3f"))
+    } else {
+        s.to_string()
+    }
+}
+
+// a year, unambiguously
+fn year(y: i32) -> String {
+    if y >= 100 {
+        y.to_string()
+    } else if y > 0 {
+        format!("{} AD", y)
+    } else {
+        format!("{} BC", 1 - y)
+    }
+}
+
+fn month(m: u32) -> &'static str {
+    [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ][m as usize - 1]
+}
@@ -0,0 +1,285 @@
+/*!
+
+Named days such as "Christmas", "Thanksgiving", and "Easter Sunday".
+
+The names of holidays are supplied to the parser by the `HolidayCalendar` of the `Config`. A
+holiday may be used wherever a day of the month such as "June 5" may be: "Christmas",
+"from Christmas Eve to New Year's Day", "the week after Labor Day". Followed by a year it is a
+particular day: "Thanksgiving 2022", "Easter, 1969". Names are matched without regard to
+case, whitespace, or apostrophes, so "valentines day" is Valentine's Day.
+
+By default the calendar is `Holidays::western()`, a set of American and Western Christian
+holidays. You may extend or replace it with days of your own, or implement `HolidayCalendar`
+yourself.
+
+# Example
+
+```rust
+# extern crate two_timer;
+# use two_timer::holidays::{Holiday, Holidays};
+# use two_timer::{parse, Config};
+let calendar = Holidays::western()
+    .add("Founders' Day", Holiday::Fixed(5, 6))
+    .add("Thanksgiving", Holiday::NthWeekday(10, chrono::Weekday::Mon, 2));
+let config = Config::new().holiday_calendar(calendar);
+let (start, _, _) = parse("Founders Day 1969", Some(config.clone())).unwrap();
+assert_eq!("1969-05-06 00:00:00", start.to_string());
+let (start, _, _) = parse("Thanksgiving 2022", Some(config)).unwrap();
+assert_eq!("2022-10-10 00:00:00", start.to_string());
+```
+
+*/
+
+use chrono::naive::NaiveDate;
+use chrono::{Datelike, Duration, Weekday};
+use regex::Regex;
+use std::sync::Arc;
+
+lazy_static! {
+    // the default calendar and the pattern matching its names, shared by every configuration
+    pub(crate) static ref WESTERN: (Arc<dyn HolidayCalendar>, Option<Arc<Regex>>) = {
+        let calendar = Holidays::western();
+        let pattern = crate::vocabulary(&[], &calendar).map(Arc::new);
+        (Arc::new(calendar), pattern)
+    };
+}
+
+/// A source of named days.
+pub trait HolidayCalendar: std::fmt::Debug + Send + Sync {
+    /// The names of the holidays, as they may appear in time expressions.
+    fn names(&self) -> Vec<String>;
+    /// The date of the named holiday in the given year, if it falls in that year. The name
+    /// is one of those provided by `names`.
+    fn date(&self, name: &str, year: i32) -> Option<NaiveDate>;
+}
+
+/// A rule by which the date of a holiday is found for a given year.
+#[derive(Debug, Clone, Copy)]
+pub enum Holiday {
+    /// A month and day: `Holiday::Fixed(12, 25)` for Christmas. A holiday on February 29th
+    /// only occurs in leap years.
+    Fixed(u32, u32),
+    /// A month and day observed on the nearest weekday when it falls on a weekend, as
+    /// American federal holidays are: Friday if it falls on Saturday, Monday if on Sunday.
+    /// New Year's Day may thus be observed on December 31 of the previous year.
+    Observed(u32, u32),
+    /// The nth weekday of a month, counting from the end of the month if n is negative:
+    /// `Holiday::NthWeekday(11, Weekday::Thu, 4)` for American Thanksgiving,
+    /// `Holiday::NthWeekday(5, Weekday::Mon, -1)` for Memorial Day.
+    NthWeekday(u32, Weekday, i32),
+    /// Some number of days from Easter Sunday in the Gregorian calendar:
+    /// `Holiday::Easter(-2)` for Good Friday.
+    Easter(i64),
+    /// Any rule at all.
+    Custom(fn(i32) -> Option<NaiveDate>),
+}
+
+impl Holiday {
+    /// The date of the holiday in the given year, if it has one.
+    pub fn date(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            Holiday::Fixed(month, day) => NaiveDate::from_ymd_opt(year, month, day),
+            Holiday::Observed(month, day) => {
+                let d = NaiveDate::from_ymd_opt(year, month, day)?;
+                match d.weekday() {
+                    Weekday::Sat => d.pred_opt(),
+                    Weekday::Sun => d.succ_opt(),
+                    _ => Some(d),
+                }
+            }
+            Holiday::NthWeekday(month, weekday, n) => {
+                if n > 0 {
+                    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                    let offset = (7 + weekday.num_days_from_monday() as i64
+                        - first.weekday().num_days_from_monday() as i64)
+                        % 7;
+                    let d = first + Duration::days(offset + 7 * (n as i64 - 1));
+                    (d.month() == month).then_some(d)
+                } else if n < 0 {
+                    let last = NaiveDate::from_ymd_opt(year, month, 1)?
+                        + Duration::days(crate::days_in_month(year, month) as i64 - 1);
+                    let offset = (7 + last.weekday().num_days_from_monday() as i64
+                        - weekday.num_days_from_monday() as i64)
+                        % 7;
+                    let d = last - Duration::days(offset + 7 * (-n as i64 - 1));
+                    (d.month() == month).then_some(d)
+                } else {
+                    None
+                }
+            }
+            Holiday::Easter(offset) => easter(year)?.checked_add_signed(Duration::days(offset)),
+            Holiday::Custom(f) => f(year),
+        }
+    }
+}
+
+/// A list of named holidays.
+#[derive(Debug, Clone, Default)]
+pub struct Holidays {
+    days: Vec<(String, Holiday)>,
+}
+
+impl Holidays {
+    /// Constructs a calendar with no holidays.
+    pub fn new() -> Holidays {
+        Holidays { days: vec![] }
+    }
+    /// Constructs a calendar of common American and Western Christian holidays. The
+    /// American federal holidays are those of the present day and are found in every year,
+    /// including years before they were established.
+    pub fn western() -> Holidays {
+        let mut calendar = Holidays::new();
+        for (names, holiday) in [
+            (&["New Year's Day", "New Year's"][..], Holiday::Fixed(1, 1)),
+            (
+                &[
+                    "Martin Luther King Day",
+                    "Martin Luther King Jr. Day",
+                    "MLK Day",
+                ][..],
+                Holiday::NthWeekday(1, Weekday::Mon, 3),
+            ),
+            (&["Valentine's Day"][..], Holiday::Fixed(2, 14)),
+            (
+                &["Presidents' Day", "Washington's Birthday"][..],
+                Holiday::NthWeekday(2, Weekday::Mon, 3),
+            ),
+            (
+                &["St. Patrick's Day", "Saint Patrick's Day"][..],
+                Holiday::Fixed(3, 17),
+            ),
+            (&["Ash Wednesday"][..], Holiday::Easter(-46)),
+            (&["Palm Sunday"][..], Holiday::Easter(-7)),
+            (&["Good Friday"][..], Holiday::Easter(-2)),
+            (&["Easter", "Easter Sunday"][..], Holiday::Easter(0)),
+            (&["Easter Monday"][..], Holiday::Easter(1)),
+            (&["Ascension Day"][..], Holiday::Easter(39)),
+            (&["Pentecost", "Whitsunday"][..], Holiday::Easter(49)),
+            (
+                &["Mother's Day"][..],
+                Holiday::NthWeekday(5, Weekday::Sun, 2),
+            ),
+            (
+                &["Memorial Day"][..],
+                Holiday::NthWeekday(5, Weekday::Mon, -1),
+            ),
+            (
+                &["Father's Day"][..],
+                Holiday::NthWeekday(6, Weekday::Sun, 3),
+            ),
+            (&["Juneteenth"][..], Holiday::Fixed(6, 19)),
+            (&["Independence Day"][..], Holiday::Fixed(7, 4)),
+            (&["Labor Day"][..], Holiday::NthWeekday(9, Weekday::Mon, 1)),
+            (
+                &["Columbus Day", "Indigenous Peoples' Day"][..],
+                Holiday::NthWeekday(10, Weekday::Mon, 2),
+            ),
+            (&["Halloween"][..], Holiday::Fixed(10, 31)),
+            (&["All Saints' Day"][..], Holiday::Fixed(11, 1)),
+            (&["Veterans Day"][..], Holiday::Fixed(11, 11)),
+            (
+                &["Thanksgiving", "Thanksgiving Day"][..],
+                Holiday::NthWeekday(11, Weekday::Thu, 4),
+            ),
+            (&["Christmas Eve"][..], Holiday::Fixed(12, 24)),
+            (
+                &["Christmas", "Christmas Day", "Xmas"][..],
+                Holiday::Fixed(12, 25),
+            ),
+            (&["Boxing Day"][..], Holiday::Fixed(12, 26)),
+            (&["New Year's Eve"][..], Holiday::Fixed(12, 31)),
+        ] {
+            for name in names {
+                calendar.days.push((name.to_string(), holiday));
+            }
+        }
+        calendar
+    }
+    /// Constructs a calendar of the American federal holidays on the days they are observed.
+    /// This is suitable for `Config::days_off`.
+    pub fn us_federal() -> Holidays {
+        let mut calendar = Holidays::new();
+        for (name, holiday) in [
+            ("New Year's Day", Holiday::Observed(1, 1)),
+            (
+                "Martin Luther King Jr. Day",
+                Holiday::NthWeekday(1, Weekday::Mon, 3),
+            ),
+            (
+                "Washington's Birthday",
+                Holiday::NthWeekday(2, Weekday::Mon, 3),
+            ),
+            ("Memorial Day", Holiday::NthWeekday(5, Weekday::Mon, -1)),
+            ("Juneteenth", Holiday::Observed(6, 19)),
+            ("Independence Day", Holiday::Observed(7, 4)),
+            ("Labor Day", Holiday::NthWeekday(9, Weekday::Mon, 1)),
+            ("Columbus Day", Holiday::NthWeekday(10, Weekday::Mon, 2)),
+            ("Veterans Day", Holiday::Observed(11, 11)),
+            ("Thanksgiving", Holiday::NthWeekday(11, Weekday::Thu, 4)),
+            ("Christmas Day", Holiday::Observed(12, 25)),
+        ] {
+            calendar.days.push((name.to_string(), holiday));
+        }
+        calendar
+    }
+    /// Returns a copy of the calendar with the named holiday added. A holiday with the same
+    /// name, ignoring case, whitespace, and apostrophes, is replaced.
+    ///
+    /// # Panics
+    ///
+    /// If the name contains no letters or digits.
+    pub fn add(&self, name: &str, holiday: Holiday) -> Holidays {
+        assert!(
+            name.chars().any(|c| c.is_alphanumeric()),
+            "a holiday must have a name, not \"{}\"",
+            name
+        );
+        let mut c = self.clone();
+        let key = crate::normalize(name);
+        c.days.retain(|(n, _)| crate::normalize(n) != key);
+        c.days.push((name.trim().to_string(), holiday));
+        c
+    }
+}
+
+impl HolidayCalendar for Holidays {
+    fn names(&self) -> Vec<String> {
+        self.days.iter().map(|(n, _)| n.clone()).collect()
+    }
+    fn date(&self, name: &str, year: i32) -> Option<NaiveDate> {
+        let key = crate::normalize(name);
+        self.days
+            .iter()
+            .find(|(n, _)| crate::normalize(n) == key)
+            .and_then(|(_, h)| h.date(year))
+    }
+}
+
+/// The date of Easter Sunday in the given year of the Gregorian calendar, as calculated by
+/// the anonymous Gregorian algorithm.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate two_timer;
+/// # use two_timer::holidays::easter;
+/// assert_eq!("2024-03-31", easter(2024).unwrap().to_string());
+/// ```
+pub fn easter(year: i32) -> Option<NaiveDate> {
+    let y = year as i64;
+    let a = y.rem_euclid(19);
+    let b = y.div_euclid(100);
+    let c = y.rem_euclid(100);
+    let d = b.div_euclid(4);
+    let e = b.rem_euclid(4);
+    let f = (b + 8).div_euclid(25);
+    let g = (b - f + 1).div_euclid(3);
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
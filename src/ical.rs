@@ -0,0 +1,473 @@
+/*!
+
+Conversion between parsed times and iCalendar, [RFC 5545](https://tools.ietf.org/html/rfc5545).
+This module requires the `ical` feature.
+
+A range becomes a VEVENT with a DTSTART and a DTEND and a `Recurrence` becomes an RRULE. Both
+may be read back, so a time expression may be round-tripped through an .ics file. A VEVENT also
+has the UID and DTSTAMP the standard requires. The UID is a hash of the DTSTART, DTEND, and
+RRULE, so the same event always has the same UID, and the DTSTAMP is the moment, in UTC, the
+VEVENT was written.
+
+Times are written as floating local times, without a zone. A range beginning and ending at
+midnight is written with dates rather than times. When a VEVENT is read, times in UTC or, with
+the `tz` feature, with a TZID are translated into the zone of the configuration.
+
+An RRULE produced from a `Recurrence` gives the days and time of day of its occurrences in
+its BY parts. A schedule which names no days, such as "monthly", has no BY parts, so a
+calendar application will take the day from the DTSTART of the event; `recurring_vevent`
+makes this the entire period, as with the occurrences of the recurrence itself. Only those
+rules which can be expressed in English as a `Recurrence` can be read. COUNT, UNTIL, and
+BYSETPOS, among other things, are not supported.
+
+# Example
+
+```rust
+# extern crate two_timer;
+# use two_timer::ical::{parse_rrule, rrule, vevent};
+# use two_timer::recurrence::Recurrence;
+# use two_timer::parse;
+let recurrence = Recurrence::new("every other Tuesday at 3 PM", None).unwrap();
+let rule = rrule(&recurrence);
+assert_eq!(
+    "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU;BYHOUR=15;BYMINUTE=0;BYSECOND=0",
+    rule
+);
+let recurrence = parse_rrule(&rule, None).unwrap();
+assert_eq!(2, recurrence.interval());
+let (start, end, _) = parse("May 6, 1969", None).unwrap();
+let event = vevent(start, end).unwrap();
+assert!(event.starts_with("BEGIN:VEVENT\r\nUID:"));
+assert!(event.ends_with(
+    "\r\nDTSTART;VALUE=DATE:19690506\r\nDTEND;VALUE=DATE:19690507\r\nEND:VEVENT\r\n"
+));
+```
+
+*/
+
+use crate::recurrence::Recurrence;
+use crate::{parse_duration, Config, Period, TimeError};
+use chrono::naive::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Duration, FixedOffset, TimeZone, Timelike, Utc, Weekday};
+
+// the iCalendar abbreviations of the days of the week
+const DAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+/// Renders a range as a VEVENT with the range's first moment as its DTSTART and the first
+/// moment after the range as its DTEND. Lines end with CRLF, as the standard requires.
+///
+/// The standard only provides for years from 0 to 9999 and whole seconds, so ranges beyond
+/// these, such as "always", are an error.
+pub fn vevent(start: NaiveDateTime, end: NaiveDateTime) -> Result<String, TimeError> {
+    event(start, end, None)
+}
+
+/// Renders the first occurrence of a recurrence at or after the anchor as a VEVENT whose
+/// RRULE describes the rest.
+pub fn recurring_vevent(
+    recurrence: &Recurrence,
+    anchor: NaiveDateTime,
+) -> Result<String, TimeError> {
+    match recurrence.iter(anchor).next() {
+        Some((start, end)) => event(start, end, Some(rrule(recurrence))),
+        None => Err(TimeError::ImpossibleDate(format!(
+            "the recurrence does not occur after {}",
+            anchor
+        ))),
+    }
+}
+
+/// Reads the DTSTART and the DTEND, or the DURATION, of the first VEVENT in the text as a
+/// range. The text may be an entire calendar or just the properties of an event. An event
+/// with a date and no end lasts the day.
+///
+/// Times in UTC are translated into the zone of the configuration, as are, with the `tz`
+/// feature, times with a TZID.
+pub fn parse_vevent(
+    text: &str,
+    config: Option<Config>,
+) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
+    let config = config.unwrap_or_default();
+    // lines beginning with whitespace continue the previous line
+    let mut lines: Vec<String> = vec![];
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(&line[1..]);
+                continue;
+            }
+        }
+        lines.push(line.to_string());
+    }
+    let in_event = lines.iter().any(|l| l.eq_ignore_ascii_case("BEGIN:VEVENT"));
+    let mut inside = !in_event;
+    let (mut start, mut end, mut duration) = (None, None, None);
+    let mut date = false;
+    for line in lines.iter() {
+        if line.eq_ignore_ascii_case("BEGIN:VEVENT") {
+            inside = true;
+            continue;
+        }
+        if line.eq_ignore_ascii_case("END:VEVENT") {
+            break;
+        }
+        if !inside {
+            continue;
+        }
+        let (name, value) = match line.split_once(':') {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let mut params = name.split(';');
+        match params.next().unwrap().to_uppercase().as_str() {
+            "DTSTART" => {
+                let (t, is_date) = ical_time(value, params, &config)?;
+                start = Some(t);
+                date = is_date;
+            }
+            "DTEND" => end = Some(ical_time(value, params, &config)?.0),
+            "DURATION" => duration = Some(parse_duration(value.trim())?),
+            _ => (),
+        }
+    }
+    let start = match start {
+        Some(t) => t,
+        None => return Err(TimeError::Parse("the VEVENT has no DTSTART".to_string())),
+    };
+    let end = match (end, duration) {
+        (Some(t), _) => t,
+        (None, Some(d)) => d.after(start)?,
+        (None, None) if date => start + Duration::days(1),
+        _ => {
+            return Err(TimeError::Parse(
+                "the VEVENT has neither a DTEND nor a DURATION".to_string(),
+            ))
+        }
+    };
+    if end < start {
+        return Err(TimeError::Misordered(format!(
+            "the VEVENT ends at {}, before it starts at {}",
+            end, start
+        )));
+    }
+    Ok((start, end))
+}
+
+/// Renders a recurrence as the value of an RRULE, such as
+/// "FREQ=MONTHLY;BYDAY=1FR" for "the first Friday of each month".
+pub fn rrule(recurrence: &Recurrence) -> String {
+    let frequency = match recurrence.frequency() {
+        Period::Year => "YEARLY",
+        Period::Month => "MONTHLY",
+        Period::Week => "WEEKLY",
+        Period::Day => "DAILY",
+        Period::Hour => "HOURLY",
+        Period::Minute => "MINUTELY",
+        _ => "SECONDLY",
+    };
+    let mut parts = vec![format!("FREQ={}", frequency)];
+    if recurrence.interval() > 1 {
+        parts.push(format!("INTERVAL={}", recurrence.interval()));
+    }
+    if let Some(month) = recurrence.month() {
+        parts.push(format!("BYMONTH={}", month));
+    }
+    if let Some(day) = recurrence.month_day() {
+        parts.push(format!("BYMONTHDAY={}", day));
+    }
+    if !recurrence.weekdays().is_empty() {
+        let position = recurrence
+            .position()
+            .map(|n| n.to_string())
+            .unwrap_or_default();
+        let days = recurrence
+            .weekdays()
+            .iter()
+            .map(|d| format!("{}{}", position, day_abbreviation(*d)))
+            .collect::<Vec<_>>();
+        parts.push(format!("BYDAY={}", days.join(",")));
+    }
+    if let Some(t) = recurrence.time() {
+        parts.push(format!("BYHOUR={}", t.hour()));
+        parts.push(format!("BYMINUTE={}", t.minute()));
+        parts.push(format!("BYSECOND={}", t.second()));
+    }
+    // Monday is the default, and only weekly schedules care
+    if recurrence.frequency() == Period::Week && !recurrence.config.monday_starts_week {
+        parts.push("WKST=SU".to_string());
+    }
+    parts.join(";")
+}
+
+/// Reads the value of an RRULE, with or without the "RRULE:" prefix, as a recurrence. The
+/// rule must describe a schedule expressible as a `Recurrence`. A WKST of SU or MO overrides
+/// the first day of the week in the configuration.
+pub fn parse_rrule(rule: &str, config: Option<Config>) -> Result<Recurrence, TimeError> {
+    let error = |problem: &str| TimeError::Parse(format!("{} in RRULE \"{}\"", problem, rule));
+    let mut value = rule.trim();
+    if value.len() >= 6 && value[..6].eq_ignore_ascii_case("RRULE:") {
+        value = &value[6..];
+    }
+    let mut recurrence = Recurrence {
+        frequency: Period::Day,
+        interval: 1,
+        weekdays: vec![],
+        position: None,
+        month_day: None,
+        month: None,
+        time: None,
+        config: config.unwrap_or_default(),
+    };
+    let mut frequency = None;
+    let mut clock: [Option<u32>; 3] = [None; 3];
+    for part in value.split(';').filter(|p| !p.is_empty()) {
+        let (name, value) = match part.split_once('=') {
+            Some(pair) => pair,
+            None => return Err(error(&format!("no value for {}", part))),
+        };
+        let name = name.to_uppercase();
+        let value = value.to_uppercase();
+        match name.as_str() {
+            "FREQ" => {
+                frequency = Some(match value.as_str() {
+                    "YEARLY" => Period::Year,
+                    "MONTHLY" => Period::Month,
+                    "WEEKLY" => Period::Week,
+                    "DAILY" => Period::Day,
+                    "HOURLY" => Period::Hour,
+                    "MINUTELY" => Period::Minute,
+                    "SECONDLY" => Period::Second,
+                    _ => return Err(error(&format!("unknown frequency {}", value))),
+                })
+            }
+            "INTERVAL" => {
+                recurrence.interval = match value.parse::<u32>() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(error(&format!("bad interval {}", value))),
+                }
+            }
+            "BYMONTH" => {
+                recurrence.month = Some(number(&name, &value, 1, 12).map_err(|e| error(&e))? as u32)
+            }
+            "BYMONTHDAY" => {
+                recurrence.month_day = Some(number(&name, &value, -31, 31).map_err(|e| error(&e))?)
+            }
+            "BYHOUR" => {
+                clock[0] = Some(number(&name, &value, 0, 23).map_err(|e| error(&e))? as u32)
+            }
+            "BYMINUTE" => {
+                clock[1] = Some(number(&name, &value, 0, 59).map_err(|e| error(&e))? as u32)
+            }
+            "BYSECOND" => {
+                clock[2] = Some(number(&name, &value, 0, 59).map_err(|e| error(&e))? as u32)
+            }
+            "BYDAY" => {
+                for day in value.split(',') {
+                    if day.len() < 2 || !day.is_char_boundary(day.len() - 2) {
+                        return Err(error(&format!("unknown day {}", day)));
+                    }
+                    let (n, d) = day.split_at(day.len() - 2);
+                    let d = match DAYS.iter().find(|(a, _)| *a == d) {
+                        Some((_, d)) => *d,
+                        None => return Err(error(&format!("unknown day {}", day))),
+                    };
+                    if !n.is_empty() {
+                        if recurrence.position.is_some() {
+                            return Err(error("more than one numbered day"));
+                        }
+                        recurrence.position =
+                            Some(number("BYDAY", n, -5, 5).map_err(|e| error(&e))?);
+                    }
+                    recurrence.weekdays.push(d);
+                }
+            }
+            "WKST" => {
+                recurrence.config.monday_starts_week = match value.as_str() {
+                    "MO" => true,
+                    "SU" => false,
+                    _ => return Err(error(&format!("unsupported week start {}", value))),
+                }
+            }
+            _ => return Err(error(&format!("unsupported part {}", name))),
+        }
+    }
+    recurrence.frequency = match frequency {
+        Some(f) => f,
+        None => return Err(error("no FREQ")),
+    };
+    // a daily rule limited to certain days is a weekly rule
+    if recurrence.frequency == Period::Day
+        && recurrence.interval == 1
+        && !recurrence.weekdays.is_empty()
+        && recurrence.position.is_none()
+    {
+        recurrence.frequency = Period::Week;
+    }
+    recurrence
+        .weekdays
+        .sort_by_key(|d| d.num_days_from_monday());
+    recurrence.weekdays.dedup();
+    let by_day = !recurrence.weekdays.is_empty();
+    let expressible = match recurrence.frequency {
+        Period::Year => !by_day && recurrence.month.is_some() == recurrence.month_day.is_some(),
+        Period::Month => {
+            recurrence.month.is_none()
+                && if by_day {
+                    recurrence.month_day.is_none()
+                        && recurrence.position.is_some()
+                        && recurrence.weekdays.len() == 1
+                } else {
+                    true
+                }
+        }
+        Period::Week => {
+            recurrence.month.is_none()
+                && recurrence.month_day.is_none()
+                && recurrence.position.is_none()
+        }
+        _ => !by_day && recurrence.month.is_none() && recurrence.month_day.is_none(),
+    };
+    if !expressible {
+        return Err(error(
+            "a combination of parts not expressible as a recurrence",
+        ));
+    }
+    if let (Some(month), Some(day)) = (recurrence.month, recurrence.month_day) {
+        // February 29th recurs, if only every four years
+        let last = crate::days_in_month(2000, month) as i32;
+        if day.abs() > last {
+            return Err(TimeError::ImpossibleDate(format!(
+                "day {} of month {} never occurs",
+                day, month
+            )));
+        }
+    }
+    if clock.iter().any(|c| c.is_some()) {
+        if recurrence.whole_periods() {
+            return Err(error("a time without days"));
+        }
+        recurrence.time = NaiveTime::from_hms_opt(
+            clock[0].unwrap_or(0),
+            clock[1].unwrap_or(0),
+            clock[2].unwrap_or(0),
+        );
+    }
+    Ok(recurrence)
+}
+
+// a VEVENT, perhaps with a recurrence rule
+fn event(
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    rule: Option<String>,
+) -> Result<String, TimeError> {
+    let dates = start.time() == NaiveTime::MIN && end.time() == NaiveTime::MIN;
+    let mut lines = format!("DTSTART{}\r\n", ical_value(start, dates)?);
+    lines += &format!("DTEND{}\r\n", ical_value(end, dates)?);
+    if let Some(rule) = rule {
+        lines += &format!("RRULE:{}\r\n", rule);
+    }
+    let mut event = "BEGIN:VEVENT\r\n".to_string();
+    event += &format!("UID:{:016x}@two-timer\r\n", fnv1a(&lines));
+    event += &format!("DTSTAMP:{}\r\n", Utc::now().format("%Y%m%dT%H%M%SZ"));
+    event += &lines;
+    event += "END:VEVENT\r\n";
+    Ok(event)
+}
+
+// the 64-bit FNV-1a hash of some text; unlike the standard library's hashers, this is the
+// same from one build to the next, so UIDs are stable
+fn fnv1a(s: &str) -> u64 {
+    s.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// the parameters and value of a DTSTART or DTEND
+fn ical_value(t: NaiveDateTime, date: bool) -> Result<String, TimeError> {
+    use chrono::Datelike;
+    if t.year() < 0 || t.year() > 9999 || t.nanosecond() != 0 {
+        return Err(TimeError::ImpossibleDate(format!(
+            "{} cannot be represented in iCalendar",
+            t
+        )));
+    }
+    Ok(if date {
+        format!(";VALUE=DATE:{}", t.format("%Y%m%d"))
+    } else {
+        format!(":{}", t.format("%Y%m%dT%H%M%S"))
+    })
+}
+
+// the moment given by the value of a DTSTART or DTEND and whether it is a date
+fn ical_time<'a>(
+    value: &str,
+    params: impl Iterator<Item = &'a str>,
+    config: &Config,
+) -> Result<(NaiveDateTime, bool), TimeError> {
+    let value = value.trim();
+    let error = || {
+        TimeError::Parse(format!(
+            "could not parse \"{}\" as an iCalendar time",
+            value
+        ))
+    };
+    let mut zone = None;
+    for param in params {
+        if let Some((name, v)) = param.split_once('=') {
+            if name.eq_ignore_ascii_case("TZID") {
+                zone = Some(v.trim_matches('"'));
+            }
+        }
+    }
+    if value.len() == 8 {
+        let d = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| error())?;
+        return Ok((d.and_time(NaiveTime::MIN), true));
+    }
+    let (local, utc) = match value.strip_suffix(|c| c == 'Z' || c == 'z') {
+        Some(v) => (v, true),
+        None => (value, false),
+    };
+    let t = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S").map_err(|_| error())?;
+    if utc {
+        let t = Utc
+            .from_utc_datetime(&t)
+            .with_timezone(&FixedOffset::east_opt(0).unwrap());
+        return Ok((config.zone.naive(&t), false));
+    }
+    match zone {
+        None => Ok((t, false)),
+        #[cfg(feature = "tz")]
+        Some(name) => match name.parse::<chrono_tz::Tz>() {
+            Ok(tz) => Ok((config.zone.naive(&crate::localize(&tz, t)), false)),
+            Err(_) => Err(TimeError::Parse(format!("unknown time zone {}", name))),
+        },
+        #[cfg(not(feature = "tz"))]
+        Some(name) => Err(TimeError::Parse(format!(
+            "the time zone {} requires the tz feature",
+            name
+        ))),
+    }
+}
+
+// an integer part of an RRULE within some bounds; only the hours, minutes, and seconds
+// of the day may be 0
+fn number(name: &str, value: &str, min: i32, max: i32) -> Result<i32, String> {
+    match value.trim_start_matches('+').parse::<i32>() {
+        Ok(n) if n >= min && n <= max && (n != 0 || min == 0) => Ok(n),
+        _ => Err(format!("bad {} {}", name, value)),
+    }
+}
+
+fn day_abbreviation(day: Weekday) -> &'static str {
+    DAYS.iter().find(|(_, d)| *d == day).unwrap().0
+}
@@ -8,7 +8,9 @@ generally interpreted as referring to a point in time, such as "3 PM", though fo
 always assumes a granularity of one second. For pointwise expression the first moment is the
 point explicitly named. The `parse` expression actually returns a 3-tuple consisting of the
 two timestamps and whether the expression is literally a range -- two time expressions
-separated by a preposition such as "to", "through", "up to", or "until".
+separated by a preposition such as "to", "through", "up to", or "until". If you need more
+information about the interpretation, such as the granularity of the expression, use the
+`parse_range` function, which returns a `TimeRange`.
 
 # Example
 
@@ -107,6 +109,11 @@ parameter `default_to_past` is set to `false`, in which case it will be assumed
 The rules of interpretation for relative time expressions in ranges will likely be refined further
 in the future.
 
+Days may also be given relative to other days: "the day after tomorrow", "the day before yesterday",
+"Tuesday after next", "the Monday before last", "next Friday week". An amount of time *from* a day
+names another day of the same length, so "two weeks from Friday" is a Friday. The day counted from
+is assumed to be after now, so this is two weeks from the coming Friday.
+
 # Clock Time
 
 The parse function interprets expressions such as "3:00" as referring to time on a 24 hour clock, so
@@ -165,6 +172,39 @@ let config = Config::new().pay_period_start(Some(reference_time.date()));
 let (t1, t2, _) = parse("next pay period", Some(config)).unwrap();
 ```
 
+# Quarters and Fiscal Years
+
+Quarters -- "Q3", "Q3 2023", "last quarter" -- are calendar quarters unless marked as fiscal:
+"fiscal Q3", "Q3 FY2024", "next fiscal quarter". By default the fiscal year is the calendar year,
+but you may set the month in which it begins. A fiscal year is named after the calendar year in which
+it ends.
+
+```rust
+# extern crate two_timer;
+# use two_timer::{parse, Config};
+let config = Config::new().fiscal_year_start(10);
+let (t1, t2, _) = parse("FY2024", Some(config)).unwrap();
+assert_eq!("2023-10-01 00:00:00", t1.to_string());
+assert_eq!("2024-10-01 00:00:00", t2.to_string());
+```
+
+# Parts of the Day
+
+"Morning", "afternoon", "evening", and "night" name spans of hours on a day: "this morning",
+"tomorrow afternoon", "Friday night". "Tonight" is this night. The hours of each part may be
+configured; a part which ends no later than it begins ends on the following day.
+
+```rust
+# extern crate two_timer;
+# use two_timer::{parse, Config, DayPart};
+# use chrono::NaiveDate;
+let now = NaiveDate::from_ymd_opt(1969, 5, 6).unwrap().and_hms_opt(12, 3, 5).unwrap();
+let config = Config::new().now(now).day_part(DayPart::Night, 22, 5);
+let (t1, t2, _) = parse("tonight", Some(config)).unwrap();
+assert_eq!("1969-05-06 22:00:00", t1.to_string());
+assert_eq!("1969-05-07 05:00:00", t2.to_string());
+```
+
 # Ambiguous Year Formats
 
 `two_timer` will try various year-month-day permutations until one of them parses given that days are in the range 1-31 and
@@ -177,9 +217,203 @@ months, 1-12. This is the order in which it tries these permutations:
 
 The potential unit separators are `/`, `.`, and `-`. Whitespace is optional.
 
+You can change this order with `Config::date_order`, which puts your preferred order first,
+or `Config::date_orders`, which also rejects any order you don't list. This affects numeric dates with
+and without years -- "5/6/69", "5/6", "1969-05-06 12:03:05".
+
+```rust
+# extern crate two_timer;
+# use two_timer::{parse, Config, DateOrder};
+let config = Config::new().date_order(DateOrder::DayMonthYear);
+let (t1, _, _) = parse("5/6/69", Some(config)).unwrap();
+assert_eq!("1969-06-05 00:00:00", t1.to_string());
+let config = Config::new().date_orders(&[DateOrder::DayMonthYear]);
+assert!(parse("12/13/69", Some(config)).is_err());
+```
+
+If you want every permutation that parses, not just the first, use `parse_all`. It returns each
+interpretation along with the `DateOrder` that produced it, so "5/6/69" yields both May 6 and June 5.
+
 # Timezones
 
-At the moment `two_timer` only produces "naive" times. Sorry about that.
+The `parse` function produces "naive" times in the time zone of the `Config`, by default the local
+time zone. You can set this zone to a fixed offset from UTC or, with the `tz` feature, an IANA time
+zone such as America/New_York. If you need zone-aware timestamps, use `parse_zoned`, or the
+`zoned_start` and `zoned_end` methods of `TimeRange`.
+
+```rust
+# extern crate two_timer;
+# extern crate chrono;
+# use two_timer::{parse_zoned, Config, Zone};
+# use chrono::FixedOffset;
+let config = Config::new().zone(Zone::Fixed(FixedOffset::west_opt(5 * 3600).unwrap()));
+let (start, end, _) = parse_zoned("May 6, 1969", Some(config)).unwrap();
+```
+
+Times may also be followed by a time zone: "3 PM UTC", "15:00 +02:00", "noon EST", "12:03:05Z".
+The expression is then interpreted in that zone and the result translated into the configured zone.
+A negative offset without a zone name, as in "12:03:05-05:00", is only recognized directly after
+seconds, as "9:00-10:00" is a range. Days are bounded by midnight in the configured zone, so a day
+on which the clock is turned forward may be only 23 hours long. A time skipped when the clock is
+turned forward is shifted forward by the length of the gap and a time which occurs twice when the
+clock is turned back is taken to be the earlier of the two.
+
+# Describing Ranges
+
+The `formatter` module provides `describe`, the inverse of `parse`, which renders a pair of timestamps
+as the most natural English phrase that `parse` interprets as that same pair: "last week", "May 6, 1969",
+"3 PM to 5 PM tomorrow", "the past 2 hours".
+
+# Log Files
+
+The `log_filter` module provides a `LogFilter` which keeps only those lines of a log whose
+timestamps fall in the range of a time expression. The `log-filter` command line tool
+exposes this to the shell.
+
+# Holidays
+
+Named days such as "Christmas", "Easter Sunday", and "Thanksgiving 2022" may be used wherever a
+day of the month such as "June 5" or "June 5, 2022" may be. They are found in the `HolidayCalendar` of
+the `Config`, by default `Holidays::western()`. The `holidays` module describes how to add your own.
+
+```rust
+# extern crate two_timer;
+# use two_timer::{parse, Config};
+let (t1, t2, _) = parse("the week after Labor Day 2023", None).unwrap();
+assert_eq!("2023-09-05 00:00:00", t1.to_string());
+assert_eq!("2023-09-12 00:00:00", t2.to_string());
+```
+
+# Named Anchors
+
+You may give names to ranges of your own, such as "the release freeze" or "sprint 42", with
+`Config::anchor`. These names may then be used wherever a specific time may be.
+
+```rust
+# extern crate two_timer;
+# use two_timer::{parse, Config};
+# use chrono::NaiveDate;
+let freeze = NaiveDate::from_ymd_opt(1969, 6, 2).unwrap().and_hms_opt(0, 0, 0).unwrap();
+let thaw = NaiveDate::from_ymd_opt(1969, 6, 9).unwrap().and_hms_opt(0, 0, 0).unwrap();
+let config = Config::new().anchor("the release freeze", freeze, thaw);
+let (t1, _, _) = parse("2 days before the release freeze", Some(config)).unwrap();
+assert_eq!("1969-05-31 00:00:00", t1.to_string());
+```
+
+# Business Days
+
+Business days, working days, workdays, and weekdays may be counted like other units:
+"3 business days from now", "2 workdays before May 12", "the working day after Christmas",
+"next business day". They are the days of the work week set by `Config::work_week`, by default
+Monday through Friday. All but weekdays also skip the days off set by `Config::days_off`. An
+expression such as "within 5 business days of May 6" is the range from the start of a time to
+the end of the last day allowed. The `business_days` function lists the business days of a
+period such as "weekdays this month".
+
+```rust
+# extern crate two_timer;
+# use two_timer::holidays::Holidays;
+# use two_timer::{parse, Config};
+# use chrono::NaiveDate;
+let now = NaiveDate::from_ymd_opt(1969, 5, 22).unwrap().and_hms_opt(12, 3, 5).unwrap();
+let config = Config::new().now(now).days_off(Holidays::us_federal());
+// skipping the weekend and Memorial Day
+let (t1, t2, _) = parse("3 business days from now", Some(config)).unwrap();
+assert_eq!("1969-05-28 00:00:00", t1.to_string());
+assert_eq!("1969-05-29 00:00:00", t2.to_string());
+```
+
+# Open-Ended Ranges
+
+A range may have only one specific end. "Before May" runs from the beginning of time, `first_moment()`,
+to the start of May. "2020 onward", "from May 6 on", "no earlier than noon", "after 2020", and "any
+time after 2020" run to the end of time, `last_moment()`. "Until Friday" and "by Friday" run from now
+to the start and end of Friday respectively. "Since May" runs from May to now.
+`TimeRange::is_unbounded_start` and `TimeRange::is_unbounded_end` say which end, if either, is the
+beginning or end of time.
+
+```rust
+# extern crate two_timer;
+# use two_timer::{parse_range, Config};
+let range = parse_range("before 2020", None).unwrap();
+assert!(range.is_unbounded_start());
+assert_eq!("2020-01-01 00:00:00", range.end().to_string());
+```
+
+# Rolling Windows
+
+"The last 7 days", "past 24 hours", "the next 3 months", and "over the previous fortnight" are
+windows ending or beginning now. By default they are exact durations, so "the last 7 days" begins
+at this time of day a week ago. With `Config::whole_windows` they consist of whole units
+adjoining the current one: "the last 7 days" is the 7 days before today and "the next 3 months"
+the 3 months after this one. A count is required where a modifier would otherwise name a
+calendar period, so "last week" is still the week before this one, but "the past week" is a
+window.
+
+```rust
+# extern crate two_timer;
+# use two_timer::{parse, Config};
+# use chrono::NaiveDate;
+let now = NaiveDate::from_ymd_opt(1969, 5, 6).unwrap().and_hms_opt(12, 3, 5).unwrap();
+let config = Config::new().now(now);
+let (t1, t2, _) = parse("the last 7 days", Some(config.clone())).unwrap();
+assert_eq!("1969-04-29 12:03:05", t1.to_string());
+assert_eq!("1969-05-06 12:03:05", t2.to_string());
+let (t1, t2, _) = parse("the last 7 days", Some(config.whole_windows(true))).unwrap();
+assert_eq!("1969-04-29 00:00:00", t1.to_string());
+assert_eq!("1969-05-06 00:00:00", t2.to_string());
+```
+
+# Days Within Periods
+
+"The first Monday of May", "the last Friday of the month", "the second Tuesday in March 2024",
+and "the last business day of the quarter" pick out a single day of a period. The period may be
+any specific period -- a month, a month and year, "next month", "Q3 2024", "1969" -- and the
+day a weekday, a business day, or simply "day", as in "the last day of February 2024" or "the
+100th day of the year". An ordinal beyond the last such day, such as "the fifth Monday" of a
+month with four, is an `ImpossibleDate` error.
+
+```rust
+# extern crate two_timer;
+# use two_timer::{parse, Config};
+# use chrono::NaiveDate;
+let now = NaiveDate::from_ymd_opt(1969, 5, 6).unwrap().and_hms_opt(12, 3, 5).unwrap();
+let config = Config::new().now(now);
+let (t1, t2, _) = parse("the last business day of the quarter", Some(config)).unwrap();
+assert_eq!("1969-06-30 00:00:00", t1.to_string());
+assert_eq!("1969-07-01 00:00:00", t2.to_string());
+```
+
+# Parts of Periods
+
+"The beginning of", "the middle of", and "the end of" a time, or "early", "mid", and "late" a
+specific period, name its first, middle, or last third: "the end of the month", "the start of
+next week", "mid-March", "early 2020", "late June". A period of three or more whole days is
+divided into whole days, so the end of a 31-day month is its last 11 days; anything shorter is
+divided evenly, so "the end of yesterday" is its last eight hours. These parts may be either end
+of a range, as in "from early May to the end of June". After "since", "before", "until", and so
+on, "the beginning of" and "the end of" are the moments the time begins and ends, so "since the
+end of May" is since June 1.
+
+```rust
+# extern crate two_timer;
+# use two_timer::{parse, Config};
+# use chrono::NaiveDate;
+let now = NaiveDate::from_ymd_opt(1969, 5, 6).unwrap().and_hms_opt(12, 3, 5).unwrap();
+let config = Config::new().now(now);
+let (t1, t2, _) = parse("the end of the month", Some(config)).unwrap();
+assert_eq!("1969-05-21 00:00:00", t1.to_string());
+assert_eq!("1969-06-01 00:00:00", t2.to_string());
+```
+
+# Recurring Times
+
+The `recurrence` module provides a `Recurrence`, a schedule such as "every Monday at 9", "daily", or
+"the first Friday of each month", whose `iter` method produces the successive ranges of the schedule
+from some moment.
+
+With the `ical` feature, the `ical` module converts ranges to and from iCalendar VEVENTs and
+recurrences to and from RRULEs.
 
 # Optional Features
 
@@ -217,9 +451,22 @@ only the typical expressions used with JobLog, falling back on the full grammar
    * yesterday
 });
 
+The `tz` feature adds the `Zone::Named` variant, allowing the use of IANA time zones via the
+[chrono-tz](https://crates.io/crates/chrono-tz) crate.
+
+The `ical` feature adds the `ical` module, which reads and writes iCalendar events and recurrence
+rules.
+
 */
 
-#![recursion_limit = "1024"]
+#![recursion_limit = "2048"]
+// the older code here predates these lints
+#![allow(
+    clippy::assign_op_pattern,
+    clippy::doc_lazy_continuation,
+    clippy::iter_nth_zero,
+    clippy::useless_conversion
+)]
 #[macro_use]
 extern crate pidgin;
 #[macro_use]
@@ -227,9 +474,162 @@ extern crate lazy_static;
 extern crate chrono;
 extern crate serde_json;
 use chrono::naive::{NaiveDate, NaiveDateTime};
-use chrono::{Datelike, Duration, Local, Timelike, Weekday};
+use chrono::offset::LocalResult;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, Offset, TimeZone, Timelike, Weekday,
+};
+use holidays::HolidayCalendar;
 use pidgin::{Grammar, Match, Matcher};
 use regex::Regex;
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+pub mod formatter;
+pub mod holidays;
+#[cfg(feature = "ical")]
+pub mod ical;
+pub mod log_filter;
+pub mod recurrence;
+
+// time zone abbreviations with their offsets from UTC in hours
+const ZONE_NAMES: [(&str, i32); 24] = [
+    ("UTC", 0),
+    ("GMT", 0),
+    ("Z", 0),
+    ("WET", 0),
+    ("WEST", 1),
+    ("BST", 1),
+    ("CET", 1),
+    ("CEST", 2),
+    ("EET", 2),
+    ("EEST", 3),
+    ("JST", 9),
+    ("AEST", 10),
+    ("AEDT", 11),
+    ("HST", -10),
+    ("AKST", -9),
+    ("AKDT", -8),
+    ("PST", -8),
+    ("PDT", -7),
+    ("MST", -7),
+    ("MDT", -6),
+    ("CST", -6),
+    ("CDT", -5),
+    ("EST", -5),
+    ("EDT", -4),
+];
+
+// an ISO 8601 calendar, week, or ordinal date joined by a T to a time, in either the extended
+// format, 2023-05-06T12:03:05.123+02:00, or the basic, 20230506T120305.123+0200
+const ISO_DATE_TIME: &str = concat!(
+    r"\b[0-9]{4}(?:",
+    r"-(?:0[1-9]|1[0-2])-(?:0[1-9]|[12][0-9]|3[01])",
+    r"|(?:0[1-9]|1[0-2])(?:0[1-9]|[12][0-9]|3[01])",
+    r"|-?[Ww](?:0[1-9]|[1-4][0-9]|5[0-3])-?[1-7]",
+    r"|-?(?:00[1-9]|0[1-9][0-9]|[12][0-9]{2}|3[0-5][0-9]|36[0-6])",
+    r")[Tt](?:[01][0-9]|2[0-4])(?::?[0-5][0-9](?::?[0-5][0-9](?:[.,][0-9]+)?)?)?",
+    r"(?:[+\-](?:[01][0-9]|2[0-3])(?::?[0-5][0-9])?)?"
+);
+
+// an ISO 8601 week date, 2023-W18-6, or ordinal date, 2023-126
+const ISO_DATE: &str = concat!(
+    r"\b[0-9]{4}(?:",
+    r"-?[Ww](?:0[1-9]|[1-4][0-9]|5[0-3])-?[1-7]",
+    r"|-(?:00[1-9]|0[1-9][0-9]|[12][0-9]{2}|3[0-5][0-9]|36[0-6])",
+    r")\b"
+);
+
+// an ISO 8601 week, 2023-W18, or month, 2023-05
+const ISO_PERIOD: &str = r"\b[0-9]{4}(?:-?[Ww](?:0[1-9]|[1-4][0-9]|5[0-3])|-(?:0[1-9]|1[0-2]))\b";
+
+// an ISO 8601 duration such as P1Y2M or PT2H30M; the smallest unit may be fractional
+const ISO_DURATION: &str = concat!(
+    r"\b[Pp]",
+    r"(?:[0-9]+(?:[.,][0-9]+)?[Yy])?",
+    r"(?:[0-9]+(?:[.,][0-9]+)?[Mm])?",
+    r"(?:[0-9]+(?:[.,][0-9]+)?[Ww])?",
+    r"(?:[0-9]+(?:[.,][0-9]+)?[Dd])?",
+    r"(?:[Tt]",
+    r"(?:[0-9]+(?:[.,][0-9]+)?[Hh])?",
+    r"(?:[0-9]+(?:[.,][0-9]+)?[Mm])?",
+    r"(?:[0-9]+(?:[.,][0-9]+)?[Ss])?",
+    r")?\b"
+);
+
+// holiday and anchor names as marked by mark
+const HOLIDAY: &str = "\u{E000}[^\u{E000}-\u{E003}]+\u{E001}";
+const ANCHOR: &str = "\u{E002}[^\u{E000}-\u{E003}]+\u{E003}";
+
+// the nth or last day of some kind in a period, as in "the second Tuesday in March"; this is a
+// single pattern rather than a rule of its own to keep the number of groups down; no period has
+// more than three digits' worth of days
+const NTH_OF: &str = concat!(
+    r"(?i:\bthe\s+(?:[1-9][0-9]{0,2}(?:st|nd|rd|th)|first|second|third|fourth|fifth|sixth|seventh",
+    r"|eighth|ninth|tenth|last)\s+(?:day|business\s+day|working\s+day|workday|weekday",
+    r"|sunday|monday|tuesday|wednesday|thursday|friday|saturday",
+    r"|sun|mon|tues?|weds?|thu|thurs?|fri|sat)\s+(?:in|of)\b)"
+);
+
+// a time of day as it is spoken: "half past three", "twenty-five minutes to six", "ten o'clock",
+// "three PM"; this is a single pattern rather than a set of rules to keep the number of groups down
+const SPOKEN_TIME: &str = concat!(
+    r"(?i:\b(?:",
+    // some minutes past or to an hour
+    r"(?:(?:a\s+)?quarter|half",
+    r"|(?:twenty[- ]five|one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve|thirteen",
+    r"|fourteen|fifteen|sixteen|seventeen|eighteen|nineteen|twenty)(?:\s*minutes?)?",
+    r"|(?:[1-5][0-9]|[1-9])\s*minutes?)",
+    r"\s*\b(?:past|after|to|till|before|of)\s*",
+    r"(?:(?:1[0-2]|[1-9])(?:\s*(?-i:am|AM|pm|PM|a\.m\.|A\.M\.|p\.m\.|P\.M\.)|\b)",
+    r"|(?:one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve)\b",
+    r"(?:\s*(?-i:am|AM|pm|PM|a\.m\.|A\.M\.|p\.m\.|P\.M\.))?)",
+    // an hour o'clock
+    r"|(?:(?:1[0-2]|[1-9])|(?:one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve))",
+    r"\s*\bo['’]?clock\b(?:\s*(?-i:am|AM|pm|PM|a\.m\.|A\.M\.|p\.m\.|P\.M\.))?",
+    // a spelled out hour with am or pm
+    r"|(?:one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve)",
+    r"\s*(?-i:am|AM|pm|PM|a\.m\.|A\.M\.|p\.m\.|P\.M\.)",
+    r"))"
+);
+
+// spelled out numbers for hours and minutes, one being the first
+const NUMBER_WORDS: [&str; 20] = [
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+];
+
+lazy_static! {
+    // an ISO 8601 interval, start/end, start/duration, or duration/end, where the start and end
+    // are dates, date times, or months, or else a duration alone; this is a single pattern
+    // rather than a set of rules to keep the number of groups down
+    static ref ISO_INTERVAL: String = {
+        let endpoint = format!(
+            r"(?:{}[Zz]?|{}|\b[0-9]{{4}}-(?:0[1-9]|1[0-2])(?:-(?:0[1-9]|[12][0-9]|3[01]))?\b|{})",
+            ISO_DATE_TIME, ISO_DATE, ISO_DURATION
+        );
+        format!("(?:{0}/{0}|{1})", endpoint, ISO_DURATION)
+    };
+}
 
 lazy_static! {
     // making this public is useful for testing, but best to keep it hidden to
@@ -247,46 +647,71 @@ lazy_static! {
 
         particular => <one_time> | <two_times>
 
-        one_time => <moment_or_period>
+        // a time, or a range with one specific end: since May, before 2020, until Friday, May 6 onward
+        one_time -> <bound>? <clusivity>? <moment_or_period> <onward>?
+
+        two_times -> ("from")? <moment_or_period> <to> <moment_or_period> | <times_on_day> | <iso_interval>
 
-        two_times -> ("from")? <moment_or_period> <to> <moment_or_period> | <since_time>
+        times_on_day -> <some_day> ("from")? <time> <to> <time> // yesterday from 2pm to 4pm
 
-        since_time -> <since> <clusivity>? <moment_or_period>
+        bound => <since> | <later_than> | <not_before> | <before> | <until> | <by>
 
         clusivity -> ("the") <terminus> ("of")
 
-        terminus => <beginning> | <end>
+        terminus => <beginning> | <middle> | <end>
 
         to => <up_to> | <through>
 
         moment_or_period => <moment> | <period>
 
-        period => <named_period> | <specific_period>
+        period => <named_period>
+        period -> <period_part>? <specific_period> // the second Tuesday in March 2024, the end of next week, mid-March
+
+        period_part => <nth_of> | <sub_period>
 
-        specific_period => <modified_period> | <month_and_year> | <year> | <relative_period>
+        sub_period -> ("the") <terminus> ("of") | <phase> // the beginning of, early, mid-, late
+
+        specific_period => <modified_period> | <month_and_year> | <year> | <relative_period> | <named_quarter> | <fiscal_year> | <iso_period> | <rolling_window>
 
         modified_period -> <modifier>? <modifiable_period>
 
-        modifiable_period => [["week", "month", "year", "pay period", "payperiod", "pp", "weekend"]] | <a_month> | <a_day>
+        modifiable_period => [["week", "month", "year", "pay period", "payperiod", "pp", "weekend", "quarter", "fiscal year", "fiscal quarter"]] | <a_month> | <a_day> | <day_part> | <business_day>
 
         month_and_year -> <a_month> <year>
 
+        named_quarter -> <fiscal>? <q_number> <year>? // Q3, Q3 2023, fiscal Q3
+        named_quarter -> <q_number> <fy>              // Q3 FY2024
+
+        fiscal_year -> <fy>                     // FY2024
+        fiscal_year -> <fiscal> ("year")? <year> // fiscal year 2024, fiscal 2024
+
+        fy -> ("FY") <year> | <fy_year>
+
         year => <short_year> | ("-")? <n_year>
         year -> <suffix_year> <year_suffix>
 
         year_suffix => <ce> | <bce>
 
         relative_period -> <count> <displacement> <from_now_or_ago>
+        relative_period -> <count> <business_unit> <from_now_or_ago> // 3 business days from now
 
         count => r(r"[1-9][0-9]*") | <a_count>
 
+        rolling_window -> <window_prefix>? ("the")? <window> <count>? <window_unit> // the last 7 days, over the past fortnight
+
+        window_unit => <any_unit> | <fortnight>
+
         named_period => <a_day> | <a_month>
 
         moment -> <adjustment>? <point_in_time>
 
         adjustment -> <amount> <direction> // two minutes before
+        adjustment -> <within> <amount> ("of") // within 5 business days of May 6
 
-        amount -> <count> <unit>
+        amount -> <count> <any_unit>
+        amount -> ("the") <any_unit> // the week after Labor Day
+
+        any_unit => <unit> | <business_unit>
 
         point_in_time -> <at_time_on>? <some_day> <at_time>? | <specific_time> | <time>
 
@@ -294,9 +719,15 @@ lazy_static! {
 
         some_day => <specific_day> | <relative_day>
 
-        specific_day => <adverb> | <date_with_year>
+        specific_day => <adverb> | <date_with_year> | <modified_day>
+
+        modified_day -> <modifier> <a_day>                // next Tuesday
+        modified_day -> ("the")? <a_day> <beyond>         // Tuesday after next, the Monday before last
+        modified_day -> <modifier>? <a_day> <week_hence>  // next Friday week, Tuesday week
 
-        date_with_year => <n_date> | <a_date>
+        date_with_year => <n_date> | <a_date> | <iso_date> | <holiday_date>
+
+        holiday_date -> <holiday> (",")? <year> // Thanksgiving 2022
 
         n_date -> <year>    r("[./-]") <n_month> r("[./-]") <n_day>
         n_date -> <year>    r("[./-]") <n_day>   r("[./-]") <n_month>
@@ -312,12 +743,18 @@ lazy_static! {
         relative_day => <a_day> | <a_day_in_month>
 
         at_time -> ("at") <time>
+        at_time -> ("in the")? <day_part> // Friday night, tomorrow in the afternoon
+
+        specific_time => <first_time> | <last_time> | <precise_time> | <anchor>
 
-        specific_time => <first_time> | <last_time> | <precise_time>
+        precise_time -> <precise_date> <hour_24> <zone>?
+        precise_time -> <iso_date_time> <zone>? // 2023-05-06T12:03:05.123Z
 
-        precise_time -> <n_date> <hour_24>
+        precise_date => <n_date> | <iso_date>
 
-        time -> <hour_12> <am_pm>? | <hour_24> | <named_time>
+        time -> <clock_time> <zone>?
+
+        clock_time -> <hour_12> <am_pm>? | <hour_24> | <named_time> | <spoken_time>
 
         hour_12 => <h12>
         hour_12 => <h12> (":") <minute>
@@ -325,48 +762,86 @@ lazy_static! {
 
         hour_24 => <h24>
         hour_24 => <h24> (":") <minute>
-        hour_24 => <h24> (":") <minute> (":") <second>
+        hour_24 => <h24> (":") <minute> (":") <second> <numeric_zone>?
 
-        a_day_in_month => <ordinal_day> | <day_and_month>
+        a_day_in_month => <ordinal_day> | <day_and_month> | <holiday>
 
         ordinal_day   -> <day_prefix>? ("the") <o_day>    // the first
 
         o_day => <n_ordinal> | <a_ordinal> | <roman>
 
         day_and_month -> <n_month> r("[./-]") <n_day>     // 5-6
+        day_and_month -> <n_day> r("[./-]") <n_month>     // 31-12
         day_and_month -> <a_month> ("the")? <o_n_day>     // June 5, June 5th, June fifth, June the fifth
         day_and_month -> ("the") <o_day> ("of") <a_month> // the 5th of June, the fifth of June
 
         o_n_day => <n_day> | <o_day>
 
+        // a bare negative offset is only recognized directly after seconds -- 12:03:05-05:00 -- lest
+        // it be confused with a range such as 9:00-10:00
+        zone => <zone_name> <zone_offset>? | <east_offset>
+
+        numeric_zone => <zone_offset>
+
         // terminal patterns
         // these are organized into single-line and multi-line patterns, with each group alphabetized
 
         // various phrases all meaning from the first measurable moment to the last
-        a_count         => [["one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten"]]
-        adverb          => [["now", "today", "tomorrow", "yesterday"]]
+        a_count         => [["a", "an", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten"]]
+        adverb          => [["now", "today", "tomorrow", "yesterday", "tonight"]]
         am_pm           => (?-ib) [["am", "AM", "pm", "PM", "a.m.", "A.M.", "p.m.", "P.M."]]
+        anchor          => r(ANCHOR)
         bce             => (?-ib) [["bce", "b.c.e.", "bc", "b.c.", "BCE", "B.C.E.", "BC", "B.C."]]
+        before          => [["before", "prior to", "earlier than"]]
         beginning       => [["beginning", "start"]]
+        beyond          => [["after next", "before last"]]
+        business_day    => [["business day", "working day", "workday", "weekday"]]
+        business_unit   => [["business day", "working day", "workday", "weekday"]] ("s")?
+        by              => [["by", "no later than", "on or before"]]
         ce              => (?-ib) [["ce", "c.e.", "ad", "a.d.", "CE", "C.E.", "AD", "A.D."]]
-        direction       -> [["before", "after", "around", "before and after"]]
-        displacement    => [["week", "day", "hour", "minute", "second"]] ("s")?   // not handling variable-width periods like months or years
+        day_part        => [["morning", "afternoon", "evening", "night"]]
+        direction       -> [["before", "after", "around", "before and after", "from"]]
+        displacement    => [["year", "month", "week", "day", "hour", "minute", "second"]] ("s")?
+        east_offset     => r(r"\+(?:[01][0-9]|2[0-3])(?::?[0-5][0-9])?")
         end             => ("end")
+        fiscal          => ("fiscal")
+        fortnight       => [["fortnight"]] ("s")?
         from_now_or_ago => [["from now", "ago"]]
+        fy_year         => r(r"\bFY(?:[0-9]{2}){1,2}\b")
         h12             => (?-B) [(1..=12).into_iter().collect::<Vec<_>>()]
-        h24             => [(1..=24).into_iter().flat_map(|i| vec![format!("{}", i), format!("{:02}", i)]).collect::<Vec<_>>()]
+        h24             => [(1..=24).into_iter().flat_map(|i| vec![format!("{}", i), format!("{:02}", i)]).chain(vec!["00".to_string()]).collect::<Vec<_>>()]
+        holiday         => r(HOLIDAY)
+        iso_date        => r(ISO_DATE)
+        iso_date_time   => r(ISO_DATE_TIME)
+        iso_interval    => r(ISO_INTERVAL) // 2023-01-01/P1M, PT2H
+        iso_period      => r(ISO_PERIOD)
+        later_than      => [["any time after", "anytime after", "later than", "after"]]
+        middle          => ("middle")
         minute          => (?-B) [ (0..60).into_iter().map(|i| format!("{:02}", i)).collect::<Vec<_>>() ]
         modifier        => [["the", "this", "last", "next"]]
         named_time      => [["noon", "midnight"]]
+        not_before      => [["not before", "no earlier than", "on or after", "from", "starting", "starting from"]]
         n_year          => r(r"\b(?:[1-9][0-9]{0,4}|0)\b")
+        nth_of          => r(NTH_OF)
+        onward          => [["onward", "onwards", "on", "or later", "and later", "or after", "and after"]]
+        phase           => [["early", "mid", "mid-", "late"]]
+        q_number        => [["Q1", "Q2", "Q3", "Q4"]]
         roman           => [["nones", "ides", "kalends"]]
-        since           => [["since", "after"]]
-        unit            => [["week", "day", "hour", "minute", "second"]] ("s")?
+        since           => ("since")
+        unit            => [["year", "month", "week", "day", "hour", "minute", "second"]] ("s")?
+        until           => [["until", "till", "up until"]]
         universal       => [["always", "ever", "all time", "forever", "from beginning to end", "from the beginning to the end"]]
         up_to           => [["to", "until", "up to", "till"]]
-        second          => (?-B) [ (0..60).into_iter().map(|i| format!("{:02}", i)).collect::<Vec<_>>() ]
+        window          => [["last", "past", "previous", "next", "coming", "following"]]
+        window_prefix   => [["over", "in", "during", "for"]]
+        week_hence      => ("week")
+        within          => ("within")
+        second          => r(r"[0-5][0-9](?:[.,][0-9]+)?")
+        spoken_time     => r(SPOKEN_TIME)
         suffix_year     => r(r"\b[1-9][0-9]{0,4}")
         through         => [["up through", "through", "thru"]] | r("-+")
+        zone_offset     => r(r"[+\-](?:[01]?[0-9]|2[0-3])(?::?[0-5][0-9])?")
+        zone_name       => (?-b) [ZONE_NAMES.iter().map(|(n, _)| n.to_string()).collect::<Vec<_>>()]
 
         a_day => (?-i) [["M", "T", "W", "R", "F", "S", "U"]]
         a_day => [
@@ -589,6 +1064,37 @@ lazy_static! {
     pub static ref SMALL_MATCHER : Matcher = SMALL_GRAMMAR.matcher().unwrap();
 }
 
+lazy_static! {
+    // lengths of time such as "3 hours 20 minutes", sharing the vocabulary of GRAMMAR
+    #[doc(hidden)]
+    pub static ref DURATION_GRAMMAR: Grammar = grammar!{
+        (?ibBw)
+
+        TOP -> r(r"\A") <duration> r(r"\z")
+
+        // a repeated rule only captures its last repetition, so the spans are enumerated
+        duration -> <span> <span>? <span>? <span>? <span>? <span>? <span>? <span>?
+        duration => <iso_duration>
+
+        span -> (",")? ("and")? <quantity> <length>
+
+        quantity => g(GRAMMAR.rule("count").unwrap()) | <decimal> | <article> | <half>
+
+        length => g(GRAMMAR.rule("unit").unwrap()) | <abbreviation> | <fortnight>
+
+        abbreviation => (?-bB) [["y", "yr", "yrs", "mo", "mos", "w", "wk", "wks", "d", "hr", "hrs", "h", "min", "mins", "m", "sec", "secs", "s"]]
+        article      => [["a", "an"]]
+        decimal      => r(r"[0-9]*\.[0-9]+")
+        fortnight    => ("fortnight") ("s")?
+        half         => [["half a", "half an"]]
+        iso_duration => r(ISO_DURATION)
+    };
+}
+lazy_static! {
+    #[doc(hidden)]
+    pub static ref DURATION_MATCHER: Matcher = DURATION_GRAMMAR.matcher().unwrap();
+}
+
 /// Simply returns whether the given phrase is parsable as a time expression. This is slightly
 /// more efficient than `parse(expression, None).is_ok()` as no parse tree is generated.
 ///
@@ -600,10 +1106,11 @@ lazy_static! {
 /// let copacetic = parsable("5/6/69");
 /// ```
 pub fn parsable(phrase: &str) -> bool {
+    let phrase = mark(phrase, holidays::WESTERN.1.as_deref());
     if cfg!(feature = "small_grammar") {
-        SMALL_MATCHER.rx.is_match(phrase) || MATCHER.rx.is_match(phrase)
+        SMALL_MATCHER.rx.is_match(&phrase) || MATCHER.rx.is_match(&phrase)
     } else {
-        MATCHER.rx.is_match(phrase)
+        MATCHER.rx.is_match(&phrase)
     }
 }
 
@@ -613,6 +1120,9 @@ pub fn parsable(phrase: &str) -> bool {
 /// The second parameter is an optional `Config` object. In general you will not need to
 /// use this except in testing or in the interpretation of pay periods.
 ///
+/// This is a thin wrapper around `parse_range`, which provides more information about
+/// the interpretation.
+///
 /// # Examples
 ///
 /// ```rust
@@ -624,6 +1134,29 @@ pub fn parse(
     phrase: &str,
     config: Option<Config>,
 ) -> Result<(NaiveDateTime, NaiveDateTime, bool), TimeError> {
+    parse_range(phrase, config).map(|r| (r.start, r.end, r.is_range))
+}
+
+/// Converts a time expression into a `TimeRange`. This provides the same timestamps as
+/// `parse` but also the granularity of the expression and the text matched.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate two_timer;
+/// # use two_timer::{parse_range, Period};
+/// let range = parse_range("May 1969", None).unwrap();
+/// assert_eq!(Period::Month, range.period());
+/// assert!(!range.is_range());
+/// ```
+pub fn parse_range(phrase: &str, config: Option<Config>) -> Result<TimeRange, TimeError> {
+    let config = config.unwrap_or_default();
+    let marked = mark(phrase, config.vocabulary.as_deref());
+    parse_marked(&marked, config).map_err(|e| e.unmark())
+}
+
+// parse a phrase in which holiday names have been marked
+fn parse_marked(phrase: &str, config: Config) -> Result<TimeRange, TimeError> {
     let parse = if cfg!(feature = "small_grammar") {
         SMALL_MATCHER
             .parse(phrase)
@@ -638,176 +1171,775 @@ pub fn parse(
         )));
     }
     let parse = parse.unwrap();
-    if parse.has("universal") {
-        return Ok((first_moment(), last_moment(), false));
+    let (start, end, is_range) = match expression_zone(&parse)? {
+        // interpret the expression in the zone it names and then translate the result into
+        // the configured zone
+        Some(offset) => {
+            let now = config.zone.localize(config.now).with_timezone(&offset);
+            let (start, end, is_range) = interpret(&parse, &config.now(now.naive_local()))?;
+            (
+                translate(start, &offset, &config.zone),
+                translate(end, &offset, &config.zone),
+                is_range,
+            )
+        }
+        None => interpret(&parse, &config)?,
+    };
+    let expression = parse.name("time_expression").unwrap_or(&parse);
+    Ok(TimeRange {
+        start,
+        end,
+        is_range,
+        period: granularity(&parse, &config),
+        text: unmark(expression.as_str()),
+        span: unmark_offset(phrase, expression.start())..unmark_offset(phrase, expression.end()),
+        zone: config.zone,
+    })
+}
+
+/// Converts a time expression into all its plausible interpretations, ordered by preference.
+/// Numeric dates such as "5/6/69" can be read with their parts in several orders. `parse`
+/// and `parse_range` take the first order which yields a well-formed date. `parse_all`
+/// returns an interpretation for every order which yields a valid date, along with the order
+/// used. Expressions without numeric dates have only one interpretation, with no order.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate two_timer;
+/// # use two_timer::{parse_all, DateOrder};
+/// let interpretations = parse_all("5/6/69", None).unwrap();
+/// assert_eq!(2, interpretations.len());
+/// let (may, order) = &interpretations[0];
+/// assert_eq!(Some(DateOrder::MonthDayYear), *order);
+/// assert_eq!("1969-05-06 00:00:00", may.start().to_string());
+/// let (june, order) = &interpretations[1];
+/// assert_eq!(Some(DateOrder::DayMonthYear), *order);
+/// assert_eq!("1969-06-05 00:00:00", june.start().to_string());
+/// ```
+pub fn parse_all(
+    phrase: &str,
+    config: Option<Config>,
+) -> Result<Vec<(TimeRange, Option<DateOrder>)>, TimeError> {
+    let config = config.unwrap_or_default();
+    // n_month only occurs in numeric dates
+    let has_numeric_date = MATCHER
+        .parse(&mark(phrase, config.vocabulary.as_deref()))
+        .map(|m| m.has("n_month"))
+        .unwrap_or(false);
+    if !has_numeric_date {
+        return parse_range(phrase, Some(config)).map(|r| vec![(r, None)]);
     }
-    let parse = parse.name("particular").unwrap();
-    let config = config.unwrap_or(Config::new());
-    if let Some(moment) = parse.name("one_time") {
-        return match handle_one_time(moment, &config) {
-            Err(e) => Err(e),
-            Ok((d1, d2, b)) => {
-                let (d3, d4) = adjust(d1, d2, moment);
-                if d1 == d3 {
-                    Ok((d1, d2, b))
-                } else {
-                    Ok((d3, d4, b))
+    let mut interpretations: Vec<(TimeRange, Option<DateOrder>)> = vec![];
+    let mut error = None;
+    for order in &config.date_orders {
+        let mut c = config.clone();
+        c.date_orders = vec![*order];
+        match parse_range(phrase, Some(c)) {
+            Ok(r) => {
+                if !interpretations
+                    .iter()
+                    .any(|(other, _)| other.start == r.start && other.end == r.end)
+                {
+                    interpretations.push((r, Some(*order)));
                 }
             }
-        };
-    }
-    if let Some(two_times) = parse.name("two_times") {
-        let mut inclusive = two_times.has("beginning");
-        let exclusive = !inclusive && two_times.has("end"); // note this is *explicitly* exclusive
-        if !(inclusive || exclusive) && (two_times.has("time") || two_times.has("precise_time")) {
-            // treating "since noon" as including 12:00:00 and "since 2am" as including 14:00:00
-            inclusive = true;
-        }
-        if let Some(previous_time) = two_times.name("since_time") {
-            if specific(previous_time) {
-                return match specific_moment(previous_time, &config) {
-                    Ok((d1, d2)) => {
-                        let t = if inclusive { d1 } else { d2 };
-                        // if *implicitly* exclusive and we find things misordered, we become inclusive
-                        let t = if !(inclusive || exclusive) && t > config.now {
-                            d1
-                        } else {
-                            t
-                        };
-                        if t > config.now {
-                            Err(TimeError::Misordered(format!(
-                                "the inferred times, {} and {}, are misordered",
-                                t, config.now
-                            )))
-                        } else {
-                            Ok((t, config.now.clone(), false))
-                        }
-                    }
-                    Err(s) => Err(s),
-                };
-            }
-            return match relative_moment(previous_time, &config, &config.now, true) {
-                Ok((d1, d2)) => {
-                    let t = if inclusive { d1 } else { d2 };
-                    let t = if !(inclusive || exclusive) && t > config.now {
-                        d1
-                    } else {
-                        t
-                    };
-                    if t > config.now {
-                        Err(TimeError::Misordered(format!(
-                            "the inferred times, {} and {}, are misordered",
-                            t, config.now
-                        )))
-                    } else {
-                        Ok((t, config.now.clone(), false))
-                    }
+            Err(e) => {
+                if error.is_none() {
+                    error = Some(e);
                 }
-                Err(s) => Err(s),
-            };
-        }
-        let first = &two_times.children().unwrap()[0];
-        let last = &two_times.children().unwrap()[2];
-        let is_through = two_times.has("through");
-        if specific(first) {
-            if specific(last) {
-                return match specific_moment(first, &config) {
-                    Ok((d1, d2)) => {
-                        let (d1, _) = adjust(d1, d2, first);
-                        match specific_moment(last, &config) {
-                            Ok((d2, d3)) => {
-                                let (d2, d3) = adjust(d2, d3, last);
-                                let d2 = pick_terminus(d2, d3, is_through);
-                                if d1 <= d2 {
-                                    Ok((d1, d2, true))
-                                } else {
-                                    Err(TimeError::Misordered(format!(
-                                        "{} is after {}",
-                                        first.as_str(),
-                                        last.as_str()
-                                    )))
-                                }
-                            }
-                            Err(s) => Err(s),
-                        }
-                    }
-                    Err(s) => Err(s),
-                };
-            } else {
-                return match specific_moment(first, &config) {
-                    Ok((d1, d2)) => {
-                        let (d1, _) = adjust(d1, d2, first);
-                        match relative_moment(last, &config, &d1, false) {
-                            Ok((d2, d3)) => {
-                                let (d2, d3) = adjust(d2, d3, last);
-                                let d2 = pick_terminus(d2, d3, is_through);
-                                Ok((d1, d2, true))
-                            }
-                            Err(s) => Err(s),
-                        }
-                    }
-                    Err(s) => Err(s),
-                };
             }
-        } else if specific(last) {
-            return match specific_moment(last, &config) {
-                Ok((d2, d3)) => {
-                    let (d2, d3) = adjust(d2, d3, last);
-                    let d2 = pick_terminus(d2, d3, is_through);
-                    match relative_moment(first, &config, &d2, true) {
-                        Ok((d1, d3)) => {
-                            let (d1, _) = adjust(d1, d3, first);
-                            Ok((d1, d2, true))
-                        }
-                        Err(s) => Err(s),
-                    }
-                }
-                Err(s) => Err(s),
-            };
-        } else {
-            // the first moment is assumed to be before now if default_to_past is true, otherwise it is after
-            return match relative_moment(first, &config, &config.now, config.default_to_past) {
-                Ok((d1, d2)) => {
-                    let (d1, _) = adjust(d1, d2, first);
-                    // the second moment is necessarily after the first moment
-                    match relative_moment(last, &config, &d1, false) {
-                        Ok((d2, d3)) => {
-                            let (d2, d3) = adjust(d2, d3, last);
-                            let d2 = pick_terminus(d2, d3, is_through);
-                            Ok((d1, d2, true))
-                        }
-                        Err(s) => Err(s),
-                    }
-                }
-                Err(s) => Err(s),
-            };
         }
     }
-    unreachable!();
+    if interpretations.is_empty() {
+        Err(error.unwrap())
+    } else {
+        Ok(interpretations)
+    }
 }
 
-/// A collection of parameters that can influence the interpretation
-/// of time expressions.
-#[derive(Debug, Clone)]
-pub struct Config {
-    now: NaiveDateTime,
-    monday_starts_week: bool,
-    period: Period,
-    pay_period_length: u32,
-    pay_period_start: Option<NaiveDate>,
-    default_to_past: bool,
+/// Lists the business days of a period: "weekdays this month", "business days in Q3",
+/// "working days from May 6 to May 20". Without such a prefix the phrase is simply a period,
+/// such as "next week", and its business days are listed. Weekdays are the days of the work
+/// week set by `Config::work_week`; business days, working days, and workdays also exclude the
+/// days set by `Config::days_off`.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate two_timer;
+/// # use two_timer::{business_days, Config};
+/// # use chrono::NaiveDate;
+/// let now = NaiveDate::from_ymd_opt(1969, 5, 6).unwrap().and_hms_opt(12, 3, 5).unwrap();
+/// let days = business_days("weekdays this month", Some(Config::new().now(now))).unwrap();
+/// assert_eq!(22, days.len());
+/// assert_eq!("1969-05-01", days[0].to_string());
+/// ```
+pub fn business_days(phrase: &str, config: Option<Config>) -> Result<Vec<NaiveDate>, TimeError> {
+    lazy_static! {
+        static ref PREFIX: Regex = Regex::new(
+            r"(?i)\A\s*(?:the\s+)?(business\s+days|working\s+days|workdays|weekdays)(?:\s+(?:in|of|during)\b)?"
+        )
+        .unwrap();
+    }
+    let config = config.unwrap_or_default();
+    let (phrase, weekdays_only) = match PREFIX.captures(phrase) {
+        Some(c) => (
+            &phrase[c.get(0).unwrap().end()..],
+            self::weekdays_only(&c[1]),
+        ),
+        None => (phrase, false),
+    };
+    let range = parse_range(phrase, Some(config.clone()))?;
+    let mut d = range.start.date();
+    let last = last_day(range.start, range.end);
+    let mut days = vec![];
+    while d <= last {
+        if config.business_day(d, weekdays_only) {
+            days.push(d);
+        }
+        d = d.succ_opt().unwrap();
+    }
+    Ok(days)
 }
 
-impl Config {
-    /// Constructs an expression with the default parameters.
-    pub fn new() -> Config {
-        Config {
-            now: Local::now().naive_local(),
+/// Converts an expression such as "3 hours 20 minutes", "1.5 days", "a fortnight", "90 min",
+/// or the ISO 8601 "PT3H20M" into a length of time. The units are those of expressions such as
+/// "2 weeks ago". A duration mentioning months or years is calendar-aware: it has no fixed
+/// length until applied to some moment.
+///
+/// Returns `TimeError::ImpossibleDate` if the duration is too long to reach any moment.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate two_timer;
+/// # extern crate chrono;
+/// # use two_timer::parse_duration;
+/// # use chrono::{Duration, NaiveDate};
+/// let duration = parse_duration("3 hours 20 minutes").unwrap();
+/// assert_eq!(Some(Duration::minutes(200)), duration.fixed());
+/// let duration = parse_duration("a month").unwrap();
+/// assert_eq!(None, duration.fixed());
+/// let jan31 = NaiveDate::from_ymd_opt(2023, 1, 31).unwrap().and_hms_opt(0, 0, 0).unwrap();
+/// assert_eq!("2023-02-28 00:00:00", duration.after(jan31).unwrap().to_string());
+/// ```
+pub fn parse_duration(phrase: &str) -> Result<CalendarDuration, TimeError> {
+    let parse = match DURATION_MATCHER.parse(phrase) {
+        Some(parse) => parse,
+        None => {
+            return Err(TimeError::Parse(format!(
+                "could not parse \"{}\" as a duration",
+                phrase
+            )))
+        }
+    };
+    if let Some(iso) = parse.name("iso_duration") {
+        return iso_duration(iso.as_str());
+    }
+    let mut months = 0.0;
+    let mut milliseconds = 0.0;
+    for span in parse.all_names("span") {
+        let quantity = span.name("quantity").unwrap();
+        let n = if let Some(decimal) = quantity.name("decimal") {
+            decimal.as_str().parse::<f64>().unwrap()
+        } else if quantity.has("article") {
+            1.0
+        } else if quantity.has("half") {
+            0.5
+        } else {
+            count(quantity)? as f64
+        };
+        let length = span.name("length").unwrap().as_str().to_lowercase();
+        let seconds = if length.starts_with('f') {
+            14 * 24 * 60 * 60
+        } else if length == "m" {
+            60
+        } else {
+            match unit_period(&length) {
+                Period::Year | Period::Month => {
+                    let m = n * if length.starts_with('y') { 12.0 } else { 1.0 };
+                    if m.fract() != 0.0 {
+                        return Err(TimeError::Parse(format!(
+                            "{} is not a whole number of months",
+                            span.as_str()
+                        )));
+                    }
+                    months += m;
+                    continue;
+                }
+                Period::Week => 7 * 24 * 60 * 60,
+                Period::Day => 24 * 60 * 60,
+                Period::Hour => 60 * 60,
+                Period::Minute => 60,
+                _ => 1,
+            }
+        };
+        milliseconds += n * seconds as f64 * 1000.0;
+    }
+    // beyond these no moment can be reached by the duration
+    if months > i32::MAX as f64 || milliseconds > i64::MAX as f64 {
+        return Err(TimeError::ImpossibleDate(format!("{} is too long", phrase)));
+    }
+    Ok(CalendarDuration {
+        months: months as i64,
+        duration: Duration::milliseconds(milliseconds.round() as i64),
+    })
+}
+
+/// Finds all the time expressions in a text, such as a log line or a chat message,
+/// returning their interpretations in the order they occur. Where expressions
+/// overlap, the first is preferred, and of those beginning at the same place, the longest.
+/// The `span` of each `TimeRange` gives its location in the text.
+///
+/// Only phrases which the grammar can parse and which can be interpreted are
+/// returned. As English is full of words which could be time expressions but usually
+/// aren't, a single word which is a bare number, one or two letters long, or a lowercase word
+/// such as "may" or "always" is ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate two_timer;
+/// # use two_timer::find_all;
+/// let found = find_all("Could we meet next Tuesday? Or maybe on 5/6/69 at noon.", None);
+/// let texts = found.iter().map(|r| r.text()).collect::<Vec<_>>();
+/// assert_eq!(vec!["next Tuesday", "5/6/69 at noon"], texts);
+/// assert_eq!(14..26, found[0].span());
+/// ```
+pub fn find_all(text: &str, config: Option<Config>) -> Vec<TimeRange> {
+    lazy_static! {
+        // the places a time expression may begin and end; holiday and anchor names are
+        // marked with private use characters
+        static ref STARTS: Regex = Regex::new(r"(?:\b\w|['+\-]\w|[\x{E000}\x{E002}])").unwrap();
+        static ref ENDS: Regex = Regex::new(r"(?:\w\b|\w\.|[\x{E001}\x{E003}])").unwrap();
+        // single words not likely to be time expressions in running text
+        static ref IMPLAUSIBLE: Regex = Regex::new(
+            r"\A(?:\d+|\w{1,2}|may|march|sun|sat|wed|mar|ever|always|forever|death|doom|infinity|eternity|perdition)\z"
+        )
+        .unwrap();
+    }
+    // the longest phrase considered, in words
+    const MAX_WORDS: usize = 20;
+    let config = config.unwrap_or_default();
+    let text = mark(text, config.vocabulary.as_deref());
+    let ends = ENDS.find_iter(&text).map(|m| m.end()).collect::<Vec<_>>();
+    let mut found = vec![];
+    let mut offset = 0;
+    for start in STARTS.find_iter(&text).map(|m| m.start()) {
+        if start < offset {
+            continue;
+        }
+        let first = ends.partition_point(|&e| e <= start);
+        let last = (first + MAX_WORDS).min(ends.len());
+        for &end in ends[first..last].iter().rev() {
+            let phrase = &text[start..end];
+            if !MATCHER.rx.is_match(phrase) || IMPLAUSIBLE.is_match(phrase) {
+                continue;
+            }
+            if let Ok(mut range) = parse_marked(phrase, config.clone()) {
+                range.span = unmark_offset(&text, start)..unmark_offset(&text, end);
+                found.push(range);
+                offset = end;
+            }
+            break;
+        }
+    }
+    found
+}
+
+/// Converts a time expression into a pair of zone-aware timestamps and a boolean indicating
+/// whether the expression was literally a range. This is equivalent to `parse` except that
+/// the timestamps are located in the time zone of the configuration.
+///
+/// `Zone` is a chrono `TimeZone`, so the timestamps keep their zone: under a zone with
+/// daylight saving time, such as `Zone::Local` or `Zone::Named`, arithmetic on them follows
+/// the zone's transitions. Use `with_timezone` to convert them into another zone, or
+/// `fixed_offset` to keep only the offset.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate two_timer;
+/// # extern crate chrono;
+/// # use two_timer::{parse_zoned, Config, Zone};
+/// # use chrono::FixedOffset;
+/// let config = Config::new().zone(Zone::Fixed(FixedOffset::east_opt(2 * 3600).unwrap()));
+/// let (start, _, _) = parse_zoned("May 6, 1969 at noon UTC", Some(config)).unwrap();
+/// assert_eq!("1969-05-06 14:00:00 +02:00", start.to_string());
+/// ```
+pub fn parse_zoned(
+    phrase: &str,
+    config: Option<Config>,
+) -> Result<(DateTime<Zone>, DateTime<Zone>, bool), TimeError> {
+    parse_range(phrase, config).map(|r| (r.zoned_start(), r.zoned_end(), r.is_range))
+}
+
+// find the time zone, if any, named in the expression
+fn expression_zone(m: &Match) -> Result<Option<FixedOffset>, TimeError> {
+    let mut zones = m.all_names("zone");
+    zones.append(&mut m.all_names("numeric_zone"));
+    let mut zones = zones
+        .into_iter()
+        .map(|zone| {
+            let mut seconds = 0;
+            if let Some(name) = zone.name("zone_name") {
+                let name = name.as_str().to_uppercase();
+                seconds += ZONE_NAMES.iter().find(|(n, _)| *n == name).unwrap().1 * 3600;
+            }
+            if let Some(o) = zone
+                .name("zone_offset")
+                .or_else(|| zone.name("east_offset"))
+            {
+                seconds += offset_seconds(o.as_str());
+            }
+            (seconds, zone.as_str())
+        })
+        .collect::<Vec<_>>();
+    // an ISO 8601 date and time carries its offset, if any, after the time
+    for t in m
+        .all_names("iso_date_time")
+        .into_iter()
+        .chain(m.all_names("iso_interval"))
+    {
+        for s in t.as_str().split('/') {
+            if let Some(seconds) = iso_offset(s) {
+                zones.push((seconds, s));
+            }
+        }
+    }
+    let mut offset: Option<FixedOffset> = None;
+    for (seconds, zone) in zones {
+        let o = match FixedOffset::east_opt(seconds) {
+            Some(o) => o,
+            None => {
+                return Err(TimeError::Parse(format!(
+                    "the time zone offset in {} is out of range",
+                    zone
+                )))
+            }
+        };
+        if offset.is_some() && offset != Some(o) {
+            return Err(TimeError::Parse(format!(
+                "more than one time zone specified in {}",
+                m.as_str()
+            )));
+        }
+        offset = Some(o);
+    }
+    Ok(offset)
+}
+
+// the offset in seconds of an ISO 8601 date and time such as 2023-05-06T12:00+02:00 or
+// 2023-05-06T10:00Z, if it has one
+fn iso_offset(s: &str) -> Option<i32> {
+    if s.starts_with(['P', 'p']) {
+        return None;
+    }
+    let time = &s[s.find(['T', 't'])?..];
+    if time.ends_with(['Z', 'z']) {
+        return Some(0);
+    }
+    time.find(['+', '-']).map(|i| offset_seconds(&time[i..]))
+}
+
+// convert an offset such as +02:00, -0500, or +1 into seconds
+fn offset_seconds(s: &str) -> i32 {
+    let digits = s[1..].replace(':', "");
+    let (hours, minutes) = if digits.len() > 2 {
+        digits.split_at(digits.len() - 2)
+    } else {
+        (digits.as_str(), "0")
+    };
+    let hours = hours.parse::<i32>().unwrap();
+    let minutes = minutes.parse::<i32>().unwrap();
+    let seconds = hours * 3600 + minutes * 60;
+    if s.starts_with('-') {
+        -seconds
+    } else {
+        seconds
+    }
+}
+
+// the form in which holiday and anchor names are compared: lowercase, without apostrophes,
+// and with single spaces
+fn normalize(name: &str) -> String {
+    name.to_lowercase()
+        .replace(['\'', '’'], "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// a pattern matching the names of anchors, captured as "anchor", and of holidays, captured as
+// "holiday"; anchors are preferred, then longer names
+fn vocabulary(
+    anchors: &[(String, NaiveDateTime, NaiveDateTime)],
+    calendar: &dyn HolidayCalendar,
+) -> Option<Regex> {
+    let groups = vec![
+        (
+            "anchor",
+            anchors.iter().map(|(n, _, _)| n.clone()).collect(),
+        ),
+        ("holiday", calendar.names()),
+    ]
+    .into_iter()
+    .filter_map(|(group, mut names): (&str, Vec<String>)| {
+        names.retain(|n| n.chars().any(|c| c.is_alphanumeric()));
+        if names.is_empty() {
+            return None;
+        }
+        names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+        let alternates = names
+            .iter()
+            .map(|name| {
+                let name = name.trim();
+                let words = name
+                    .split_whitespace()
+                    .map(|w| {
+                        w.chars()
+                            .map(|c| match c {
+                                '\'' | '’' => "['’]?".to_string(),
+                                _ => regex::escape(&c.to_string()),
+                            })
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(r"\s+");
+                let start = if name.starts_with(char::is_alphanumeric) {
+                    r"\b"
+                } else {
+                    ""
+                };
+                let end = if name.ends_with(char::is_alphanumeric) {
+                    r"\b"
+                } else {
+                    ""
+                };
+                format!("{}{}{}", start, words, end)
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+        Some(format!("(?P<{}>{})", group, alternates))
+    })
+    .collect::<Vec<_>>();
+    if groups.is_empty() {
+        None
+    } else {
+        Regex::new(&format!("(?i){}", groups.join("|"))).ok()
+    }
+}
+
+// Wrap the holiday names in a phrase in the private use characters U+E000 and U+E001, and the
+// anchor names in U+E002 and U+E003, so the grammar, which cannot know the names in advance,
+// can recognize them. As the names themselves are kept, removing these characters restores
+// the phrase.
+fn mark<'a>(phrase: &'a str, vocabulary: Option<&Regex>) -> Cow<'a, str> {
+    match vocabulary {
+        Some(rx) => rx.replace_all(phrase, |c: &regex::Captures| {
+            if c.name("anchor").is_some() {
+                format!("\u{E002}{}\u{E003}", &c[0])
+            } else {
+                format!("\u{E000}{}\u{E001}", &c[0])
+            }
+        }),
+        None => Cow::Borrowed(phrase),
+    }
+}
+
+// the text of a marked phrase without the marks
+fn unmark(text: &str) -> String {
+    text.replace(['\u{E000}', '\u{E001}', '\u{E002}', '\u{E003}'], "")
+}
+
+// the offset in the unmarked phrase corresponding to an offset in the marked phrase
+fn unmark_offset(marked: &str, offset: usize) -> usize {
+    let marks = marked[..offset]
+        .matches(['\u{E000}', '\u{E001}', '\u{E002}', '\u{E003}'])
+        .count();
+    offset - marks * '\u{E000}'.len_utf8()
+}
+
+// move a naive time from a fixed offset into the given zone
+fn translate(t: NaiveDateTime, offset: &FixedOffset, zone: &Zone) -> NaiveDateTime {
+    if t == first_moment() || t == last_moment() {
+        t
+    } else {
+        zone.naive(&offset.from_local_datetime(&t).unwrap())
+    }
+}
+
+// convert a parse tree into a pair of timestamps and whether the expression is a range
+fn interpret(
+    parse: &Match,
+    config: &Config,
+) -> Result<(NaiveDateTime, NaiveDateTime, bool), TimeError> {
+    if parse.has("universal") {
+        return Ok((first_moment(), last_moment(), false));
+    }
+    let parse = parse.name("particular").unwrap();
+    if let Some(moment) = parse.name("one_time") {
+        let clusivity = moment.name("clusivity");
+        if moment.has("bound") || moment.has("onward") {
+            // "since the end of May" is since June begins, "since mid-May" since the middle third
+            // of May begins
+            let part = clusivity.or_else(|| moment.name("sub_period"));
+            let exclusive = part.is_some_and(|c| c.has("end")); // note this is *explicitly* exclusive
+            let mut inclusive = part.is_some() && !exclusive;
+            if !(inclusive || exclusive) && (moment.has("time") || moment.has("precise_time")) {
+                // treating "since noon" as including 12:00:00 and "since 2am" as including 14:00:00
+                inclusive = true;
+            }
+            let (d1, d2) = open_range(moment, inclusive, exclusive, config)?;
+            return Ok((d1, d2, false));
+        }
+        return match handle_one_time(moment, config) {
+            Err(e) => Err(e),
+            Ok((d1, d2, b)) => {
+                let (d3, d4) = adjust(d1, d2, moment, config)?;
+                // "the end of May 6" is the last third of the day
+                let (d3, d4) = match clusivity {
+                    Some(c) => part_of(c, d3, d4),
+                    None => (d3, d4),
+                };
+                if d1 == d3 && d2 == d4 {
+                    Ok((d1, d2, b))
+                } else {
+                    // "within 5 days of May 6" is a range
+                    Ok((d3, d4, b || moment.has("within")))
+                }
+            }
+        };
+    }
+    if let Some(two_times) = parse.name("two_times") {
+        if let Some(interval) = two_times.name("iso_interval") {
+            let (d1, d2) = iso_interval(interval, config)?;
+            return Ok((d1, d2, true));
+        }
+        let is_through = two_times.has("through");
+        if let Some(times_on_day) = two_times.name("times_on_day") {
+            let some_day = times_on_day.name("some_day").unwrap();
+            let (d, _) = if specific(some_day) {
+                specific_moment(some_day, config)?
+            } else {
+                relative_moment(some_day, config, &config.now, config.default_to_past)?
+            };
+            let times = times_on_day.all_names("time");
+            let day = config.now(d);
+            let (d1, _) = moment_and_time(&day, Some(times[0]));
+            let (d2, d3) = moment_and_time(&day, Some(times[1]));
+            let d2 = pick_terminus(d2, d3, is_through);
+            return if d1 <= d2 {
+                Ok((d1, d2, true))
+            } else {
+                Err(TimeError::Misordered(format!(
+                    "{} is after {}",
+                    times[0].as_str(),
+                    times[1].as_str()
+                )))
+            };
+        }
+        let first = &two_times.children().unwrap()[0];
+        let last = &two_times.children().unwrap()[2];
+        if specific(first) {
+            if specific(last) {
+                return match specific_moment(first, config) {
+                    Ok((d1, d2)) => {
+                        let (d1, _) = adjust(d1, d2, first, config)?;
+                        match specific_moment(last, config) {
+                            Ok((d2, d3)) => {
+                                let (d2, d3) = adjust(d2, d3, last, config)?;
+                                let d2 = pick_terminus(d2, d3, is_through);
+                                if d1 <= d2 {
+                                    Ok((d1, d2, true))
+                                } else {
+                                    Err(TimeError::Misordered(format!(
+                                        "{} is after {}",
+                                        first.as_str(),
+                                        last.as_str()
+                                    )))
+                                }
+                            }
+                            Err(s) => Err(s),
+                        }
+                    }
+                    Err(s) => Err(s),
+                };
+            } else {
+                return match specific_moment(first, config) {
+                    Ok((d1, d2)) => {
+                        let (d1, _) = adjust(d1, d2, first, config)?;
+                        match relative_moment(last, config, &d1, false) {
+                            Ok((d2, d3)) => {
+                                let (d2, d3) = adjust(d2, d3, last, config)?;
+                                let d2 = pick_terminus(d2, d3, is_through);
+                                Ok((d1, d2, true))
+                            }
+                            Err(s) => Err(s),
+                        }
+                    }
+                    Err(s) => Err(s),
+                };
+            }
+        } else if specific(last) {
+            return match specific_moment(last, config) {
+                Ok((d2, d3)) => {
+                    let (d2, d3) = adjust(d2, d3, last, config)?;
+                    let d2 = pick_terminus(d2, d3, is_through);
+                    match relative_moment(first, config, &d2, true) {
+                        Ok((d1, d3)) => {
+                            let (d1, _) = adjust(d1, d3, first, config)?;
+                            Ok((d1, d2, true))
+                        }
+                        Err(s) => Err(s),
+                    }
+                }
+                Err(s) => Err(s),
+            };
+        } else {
+            // the first moment is assumed to be before now if default_to_past is true, otherwise it is after
+            return match relative_moment(first, config, &config.now, config.default_to_past) {
+                Ok((d1, d2)) => {
+                    let (d1, _) = adjust(d1, d2, first, config)?;
+                    // the second moment is necessarily after the first moment
+                    match relative_moment(last, config, &d1, false) {
+                        Ok((d2, d3)) => {
+                            let (d2, d3) = adjust(d2, d3, last, config)?;
+                            let d2 = pick_terminus(d2, d3, is_through);
+                            Ok((d1, d2, true))
+                        }
+                        Err(s) => Err(s),
+                    }
+                }
+                Err(s) => Err(s),
+            };
+        }
+    }
+    unreachable!();
+}
+
+// interpret a range with only one specific end, such as "since May", "before 2020", or
+// "May 6 onward"; the other end is now or the beginning or end of time
+fn open_range(
+    m: &Match,
+    inclusive: bool,
+    exclusive: bool,
+    config: &Config,
+) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
+    let onward = m.has("onward");
+    let bound = m.name("bound").unwrap_or(m);
+    let since = bound.has("since") && !onward;
+    let ends = bound.has("before") || bound.has("until") || bound.has("by");
+    if ends && onward {
+        return Err(TimeError::Parse(format!(
+            "\"{}\" has no specific end",
+            m.as_str()
+        )));
+    }
+    // "since Friday" is the last Friday, "until Friday" the next
+    let before = if since {
+        true
+    } else if bound.has("before") {
+        config.default_to_past
+    } else {
+        false
+    };
+    let (d1, d2) = if specific(m) {
+        specific_moment(m, config)?
+    } else {
+        relative_moment(m, config, &config.now, before)?
+    };
+    let (d1, d2) = adjust(d1, d2, m, config)?;
+    let (d1, d2) = match m.name("clusivity") {
+        Some(c) => part_of(c, d1, d2),
+        None => (d1, d2),
+    };
+    // the moments before and after the time, as "the beginning of" and "the end of" allow
+    let early = if exclusive { d2 } else { d1 };
+    let late = if inclusive { d1 } else { d2 };
+    if since {
+        // if *implicitly* exclusive and we find things misordered, we become inclusive
+        let t = if !(inclusive || exclusive) && late > config.now {
+            d1
+        } else {
+            late
+        };
+        return if t > config.now {
+            Err(TimeError::Misordered(format!(
+                "the inferred times, {} and {}, are misordered",
+                t, config.now
+            )))
+        } else {
+            Ok((t, config.now))
+        };
+    }
+    if bound.has("before") {
+        return Ok((first_moment(), early));
+    }
+    if ends {
+        let t = if bound.has("by") { late } else { early };
+        return if t < config.now {
+            Err(TimeError::Misordered(format!(
+                "the inferred times, {} and {}, are misordered",
+                config.now, t
+            )))
+        } else {
+            Ok((config.now, t))
+        };
+    }
+    let t = if bound.has("later_than") { late } else { early };
+    Ok((t, last_moment()))
+}
+
+/// A collection of parameters that can influence the interpretation
+/// of time expressions.
+#[derive(Debug, Clone)]
+pub struct Config {
+    now: NaiveDateTime,
+    monday_starts_week: bool,
+    period: Period,
+    pay_period_length: u32,
+    pay_period_start: Option<NaiveDate>,
+    default_to_past: bool,
+    zone: Zone,
+    fiscal_year_start: u32,
+    date_orders: Vec<DateOrder>,
+    day_parts: [(u32, u32); 4],
+    holidays: Arc<dyn HolidayCalendar>,
+    anchors: Vec<(String, NaiveDateTime, NaiveDateTime)>,
+    vocabulary: Option<Arc<Regex>>,
+    work_week: Vec<Weekday>,
+    days_off: Option<Arc<dyn HolidayCalendar>>,
+    whole_windows: bool,
+}
+
+impl Config {
+    /// Constructs an expression with the default parameters.
+    pub fn new() -> Config {
+        Config {
+            now: Local::now().naive_local(),
             monday_starts_week: true,
             period: Period::Minute,
             pay_period_length: 7,
             pay_period_start: None,
             default_to_past: true,
+            zone: Zone::Local,
+            fiscal_year_start: 1,
+            date_orders: DateOrder::ALL.to_vec(),
+            day_parts: [(6, 12), (12, 17), (17, 21), (21, 6)],
+            holidays: holidays::WESTERN.0.clone(),
+            anchors: vec![],
+            vocabulary: holidays::WESTERN.1.clone(),
+            work_week: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            days_off: None,
+            whole_windows: false,
         }
     }
     /// Returns a copy of the configuration parameters with the "now" moment
@@ -858,6 +1990,435 @@ impl Config {
         c.default_to_past = default_to_past;
         c
     }
+    /// Returns a copy of the configuration parameters with the time zone set to the
+    /// parameter supplied. By default this is the local time zone. Expressions which
+    /// do not name a time zone are interpreted in this zone and the timestamps returned
+    /// are times in this zone. "Now" remains the same moment, so it is translated into the
+    /// new zone: noon in a zone two hours east of UTC becomes 10 AM in UTC.
+    pub fn zone(&self, zone: Zone) -> Config {
+        let mut c = self.clone();
+        c.zone = zone;
+        c.now = zone.naive(&self.zone.localize(self.now));
+        c
+    }
+    /// Returns a copy of the configuration parameters with the preferred order of the
+    /// parts of numeric dates such as "5/6/69" set to the parameter supplied. This order
+    /// is tried first. The other orders are tried after it in their default order, so
+    /// "13/5/69" will still be understood as May 13 if you prefer `DateOrder::MonthDayYear`.
+    /// Dates without a year, such as "5/6", use the relative order of the month and day.
+    pub fn date_order(&self, order: DateOrder) -> Config {
+        let mut c = self.clone();
+        c.date_orders = vec![order];
+        c.date_orders
+            .extend(DateOrder::ALL.iter().filter(|&&o| o != order));
+        c
+    }
+    /// Returns a copy of the configuration parameters with the orders of the parts of
+    /// numeric dates set to the parameter supplied. These orders are tried in sequence and
+    /// any order not listed is rejected, so with `&[DateOrder::DayMonthYear]` "12/13/2023"
+    /// cannot be parsed. By default all orders are tried: year-month-day, year-day-month,
+    /// month-day-year, and day-month-year.
+    ///
+    /// # Panics
+    ///
+    /// If no order is supplied.
+    pub fn date_orders(&self, orders: &[DateOrder]) -> Config {
+        assert!(!orders.is_empty(), "at least one date order is required");
+        let mut c = self.clone();
+        c.date_orders = orders.to_vec();
+        c
+    }
+    /// Returns a copy of the configuration parameters with the hours bounding a part of
+    /// the day set to the parameters supplied. The part begins at the first hour and ends at,
+    /// but excludes, the second. If the second hour is no later than the first, the part ends
+    /// on the following day. By default morning is 6-12, afternoon 12-17, evening 17-21, and
+    /// night 21-6.
+    ///
+    /// # Panics
+    ///
+    /// If either hour is greater than 24.
+    pub fn day_part(&self, part: DayPart, start: u32, end: u32) -> Config {
+        assert!(
+            start <= 24 && end <= 24,
+            "day parts must be bounded by hours in the range 0-24, not {} and {}",
+            start,
+            end
+        );
+        let mut c = self.clone();
+        c.day_parts[part as usize] = (start, end);
+        c
+    }
+    /// Returns a copy of the configuration parameters with the month in which the
+    /// fiscal year begins set to the parameter supplied, January being 1. By default the
+    /// fiscal year is the calendar year. A fiscal year is named after the calendar year
+    /// in which it ends, so if the fiscal year begins in October, FY2024 begins on
+    /// October 1, 2023. Fiscal quarters are aligned to the start of the fiscal year.
+    ///
+    /// # Panics
+    ///
+    /// If the month is not in the range 1-12.
+    pub fn fiscal_year_start(&self, month: u32) -> Config {
+        assert!(
+            (1..=12).contains(&month),
+            "the fiscal year must begin in a month numbered 1-12, not {}",
+            month
+        );
+        let mut c = self.clone();
+        c.fiscal_year_start = month;
+        c
+    }
+    /// Returns a copy of the configuration parameters with the calendar of named days,
+    /// such as "Christmas" or "Thanksgiving", set to the parameter supplied. By default this
+    /// is `Holidays::western()`. Use `Holidays::new()` to recognize no holidays at all.
+    pub fn holiday_calendar<C: HolidayCalendar + 'static>(&self, calendar: C) -> Config {
+        let mut c = self.clone();
+        c.vocabulary = vocabulary(&c.anchors, &calendar).map(Arc::new);
+        c.holidays = Arc::new(calendar);
+        c
+    }
+    /// Returns a copy of the configuration parameters with a named range, such as
+    /// "the release freeze" or "sprint 42", added. The name may then be used wherever a
+    /// specific time such as "May 6, 1969 at 3:52 AM" may be: "2 days before the release
+    /// freeze", "since the offsite", "from sprint 42 to Christmas". Names are matched without
+    /// regard to case, whitespace, or apostrophes. A range with the same name is replaced.
+    /// A moment, like the times `parse` produces for "3 PM", is a range one second long.
+    ///
+    /// # Panics
+    ///
+    /// If the name contains no letters or digits or the range ends before it starts.
+    pub fn anchor(&self, name: &str, start: NaiveDateTime, end: NaiveDateTime) -> Config {
+        assert!(
+            name.chars().any(|c| c.is_alphanumeric()),
+            "an anchor must have a name, not \"{}\"",
+            name
+        );
+        assert!(
+            start <= end,
+            "the range of {} ends at {}, before it starts at {}",
+            name,
+            end,
+            start
+        );
+        let mut c = self.clone();
+        let key = normalize(name);
+        c.anchors.retain(|(n, _, _)| normalize(n) != key);
+        c.anchors.push((name.trim().to_string(), start, end));
+        c.vocabulary = vocabulary(&c.anchors, c.holidays.as_ref()).map(Arc::new);
+        c
+    }
+    /// Returns a copy of the configuration parameters with the days of the work week set to
+    /// the parameter supplied. These are the days counted as "weekdays", "business days",
+    /// "working days", and "workdays". By default the work week is Monday through Friday.
+    ///
+    /// # Panics
+    ///
+    /// If no day is supplied.
+    pub fn work_week(&self, days: &[Weekday]) -> Config {
+        assert!(!days.is_empty(), "the work week must have at least one day");
+        let mut c = self.clone();
+        c.work_week = days.to_vec();
+        c.work_week.sort_by_key(|d| d.num_days_from_monday());
+        c.work_week.dedup();
+        c
+    }
+    /// Returns a copy of the configuration parameters with the holidays on which no business
+    /// is done set to the parameter supplied. Business days, working days, and workdays skip
+    /// these holidays; weekdays do not. By default there are none. This calendar is
+    /// independent of the one set by `holiday_calendar`, which supplies the names that may
+    /// be used in time expressions, so "Valentine's Day" may be a name without being a day off.
+    pub fn days_off<C: HolidayCalendar + 'static>(&self, calendar: C) -> Config {
+        let mut c = self.clone();
+        c.days_off = Some(Arc::new(calendar));
+        c
+    }
+    /// Returns a copy of the configuration parameters with whether rolling windows such as
+    /// "the last 7 days" or "the next 3 months" consist of whole units set to the parameter
+    /// supplied. By default they do not: "the last 7 days" is the 168 hours ending now. If
+    /// this parameter is set to `true`, it is instead the 7 days before today, and "the next
+    /// 3 months" the 3 months after this one. Windows of business days always consist of
+    /// whole days.
+    pub fn whole_windows(&self, whole_windows: bool) -> Config {
+        let mut c = self.clone();
+        c.whole_windows = whole_windows;
+        c
+    }
+    /// Whether the date is a business day: a day of the work week which is not a day off.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate two_timer;
+    /// # use two_timer::holidays::Holidays;
+    /// # use two_timer::Config;
+    /// # use chrono::NaiveDate;
+    /// let config = Config::new().days_off(Holidays::us_federal());
+    /// let independence_day = NaiveDate::from_ymd_opt(2024, 7, 4).unwrap();
+    /// assert!(!config.is_business_day(independence_day));
+    /// assert!(config.is_business_day(independence_day.succ_opt().unwrap()));
+    /// ```
+    pub fn is_business_day(&self, d: NaiveDate) -> bool {
+        self.business_day(d, false)
+    }
+    // whether the date counts as a business day; "weekdays" only consider the work week
+    fn business_day(&self, d: NaiveDate, weekdays_only: bool) -> bool {
+        if !self.work_week.contains(&d.weekday()) {
+            return false;
+        }
+        if weekdays_only {
+            return true;
+        }
+        match &self.days_off {
+            None => true,
+            // an observed holiday may fall in an adjacent year
+            Some(calendar) => !calendar.names().iter().any(|name| {
+                (d.year() - 1..=d.year() + 1).any(|y| calendar.date(name, y) == Some(d))
+            }),
+        }
+    }
+}
+
+/// A named part of the day, such as "morning" in "tomorrow morning". The hours of each part
+/// can be set with `Config::day_part`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayPart {
+    /// 6 AM to noon by default.
+    Morning,
+    /// Noon to 5 PM by default.
+    Afternoon,
+    /// 5 PM to 9 PM by default.
+    Evening,
+    /// 9 PM to 6 AM the following day by default. "Tonight" is this night.
+    Night,
+}
+
+impl DayPart {
+    fn from_match(m: &Match) -> DayPart {
+        match m.as_str().chars().next().expect("empty string") {
+            'm' | 'M' => DayPart::Morning,
+            'a' | 'A' => DayPart::Afternoon,
+            'e' | 'E' => DayPart::Evening,
+            _ => DayPart::Night,
+        }
+    }
+}
+
+/// The order of the year, month, and day in a numeric date such as "5/6/69".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    /// 1969/5/6
+    YearMonthDay,
+    /// 1969/6/5
+    YearDayMonth,
+    /// 5/6/1969
+    MonthDayYear,
+    /// 6/5/1969
+    DayMonthYear,
+}
+
+impl DateOrder {
+    // the orders in which they are tried by default
+    const ALL: [DateOrder; 4] = [
+        DateOrder::YearMonthDay,
+        DateOrder::YearDayMonth,
+        DateOrder::MonthDayYear,
+        DateOrder::DayMonthYear,
+    ];
+    // the year, month, and day in the parts of a numeric date, if the parts are well-formed
+    // in this order
+    fn ymd(&self, parts: &[&str], config: &Config) -> Option<(i32, u32, u32)> {
+        let (y, m, d) = match self {
+            DateOrder::YearMonthDay => (parts[0], parts[1], parts[2]),
+            DateOrder::YearDayMonth => (parts[0], parts[2], parts[1]),
+            DateOrder::MonthDayYear => (parts[2], parts[0], parts[1]),
+            DateOrder::DayMonthYear => (parts[2], parts[1], parts[0]),
+        };
+        Some((year_part(y, config)?, month_part(m)?, day_part(d)?))
+    }
+}
+
+/// A time zone in which to interpret time expressions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    /// The local time zone of the machine.
+    Local,
+    /// A fixed offset from UTC.
+    Fixed(FixedOffset),
+    /// An IANA time zone, such as America/New_York. This requires the `tz` feature.
+    #[cfg(feature = "tz")]
+    Named(chrono_tz::Tz),
+}
+
+impl Zone {
+    // locate a naive time in this zone
+    fn localize(&self, t: NaiveDateTime) -> DateTime<Zone> {
+        if t == first_moment() || t == last_moment() {
+            // the offset could push these out of the representable range
+            return DateTime::from_naive_utc_and_offset(
+                t,
+                self.offset(FixedOffset::east_opt(0).unwrap()),
+            );
+        }
+        localize(self, t)
+    }
+    // the naive time in this zone corresponding to a moment
+    fn naive<Tz: TimeZone>(&self, t: &DateTime<Tz>) -> NaiveDateTime {
+        t.with_timezone(self).naive_local()
+    }
+    // the offset of some moment in this zone
+    fn offset(&self, offset: FixedOffset) -> ZoneOffset {
+        ZoneOffset {
+            zone: *self,
+            offset,
+        }
+    }
+}
+
+impl TimeZone for Zone {
+    type Offset = ZoneOffset;
+    fn from_offset(offset: &ZoneOffset) -> Zone {
+        offset.zone
+    }
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<ZoneOffset> {
+        self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+    }
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<ZoneOffset> {
+        match self {
+            Zone::Local => Local
+                .offset_from_local_datetime(local)
+                .map(|o| self.offset(o)),
+            Zone::Fixed(o) => LocalResult::Single(self.offset(*o)),
+            #[cfg(feature = "tz")]
+            Zone::Named(tz) => tz
+                .offset_from_local_datetime(local)
+                .map(|o| self.offset(o.fix())),
+        }
+    }
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> ZoneOffset {
+        self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+    }
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ZoneOffset {
+        self.offset(match self {
+            Zone::Local => Local.offset_from_utc_datetime(utc),
+            Zone::Fixed(o) => *o,
+            #[cfg(feature = "tz")]
+            Zone::Named(tz) => tz.offset_from_utc_datetime(utc).fix(),
+        })
+    }
+}
+
+/// The offset from UTC of a moment in a `Zone`. Unlike a `FixedOffset`, it remembers its
+/// zone, so a `DateTime<Zone>` follows the zone's daylight saving time transitions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZoneOffset {
+    zone: Zone,
+    offset: FixedOffset,
+}
+
+impl Offset for ZoneOffset {
+    fn fix(&self) -> FixedOffset {
+        self.offset
+    }
+}
+
+impl fmt::Display for ZoneOffset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.offset.fmt(f)
+    }
+}
+
+// Locate a naive time in a time zone. Where the time is ambiguous because the clock is
+// turned back, the earlier moment is chosen. Where the time falls into the gap made when the
+// clock is turned forward, it is shifted forward by the length of the gap, so the first moment of
+// a day which begins with such a gap is the first moment after the gap.
+fn localize<Tz: TimeZone>(tz: &Tz, t: NaiveDateTime) -> DateTime<Tz> {
+    match tz.from_local_datetime(&t) {
+        LocalResult::Single(d) => d,
+        LocalResult::Ambiguous(d1, d2) => {
+            if d1 < d2 {
+                d1
+            } else {
+                d2
+            }
+        }
+        LocalResult::None => {
+            let before = tz.offset_from_utc_datetime(&(t - Duration::days(1))).fix();
+            tz.from_utc_datetime(&(t - before))
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::new()
+    }
+}
+
+/// The interpretation of a time expression.
+///
+/// This provides the same pair of timestamps and range flag as `parse`, plus
+/// the granularity of the expression and the text matched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeRange {
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    is_range: bool,
+    period: Period,
+    text: String,
+    span: Range<usize>,
+    zone: Zone,
+}
+
+impl TimeRange {
+    /// The first moment of the range.
+    pub fn start(&self) -> NaiveDateTime {
+        self.start
+    }
+    /// The first moment after the range.
+    pub fn end(&self) -> NaiveDateTime {
+        self.end
+    }
+    /// Whether the expression was literally a range, such as "9 to 11", as opposed to
+    /// "9 AM", say.
+    pub fn is_range(&self) -> bool {
+        self.is_range
+    }
+    /// Whether the range extends back to the beginning of time, as "before May" does.
+    /// The start of such a range is `first_moment()`.
+    pub fn is_unbounded_start(&self) -> bool {
+        self.start == first_moment()
+    }
+    /// Whether the range extends forward to the end of time, as "2020 onward" does.
+    /// The end of such a range is `last_moment()`.
+    pub fn is_unbounded_end(&self) -> bool {
+        self.end == last_moment()
+    }
+    /// The granularity of the expression: `Period::Day` for "May 6, 1969", `Period::Second`
+    /// for "3 PM", and so on. For a range this is the finer granularity of its two ends.
+    /// Expressions such as "always" which name no unit of time are given the coarsest
+    /// granularity, `Period::Year`.
+    pub fn period(&self) -> Period {
+        self.period
+    }
+    /// The portion of the phrase parsed as a time expression, excluding surrounding whitespace.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    /// The byte offsets of `text` in the phrase parsed or, for the results of
+    /// `find_all`, in the text searched.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+    /// The first moment of the range located in the configured time zone.
+    ///
+    /// The first and last moments of time are given in UTC, as a time zone offset may
+    /// push them out of the range of representable times.
+    pub fn zoned_start(&self) -> DateTime<Zone> {
+        self.zone.localize(self.start)
+    }
+    /// The first moment after the range located in the configured time zone.
+    pub fn zoned_end(&self) -> DateTime<Zone> {
+        self.zone.localize(self.end)
+    }
 }
 
 /// A simple categorization of things that could go wrong.
@@ -891,6 +2452,16 @@ impl TimeError {
             TimeError::NoPayPeriod(s) => s.as_ref(),
         }
     }
+    // restore any holiday names marked in the message
+    fn unmark(self) -> TimeError {
+        match self {
+            TimeError::Parse(s) => TimeError::Parse(unmark(&s)),
+            TimeError::Misordered(s) => TimeError::Misordered(unmark(&s)),
+            TimeError::ImpossibleDate(s) => TimeError::ImpossibleDate(unmark(&s)),
+            TimeError::Weekday(s) => TimeError::Weekday(unmark(&s)),
+            TimeError::NoPayPeriod(s) => TimeError::NoPayPeriod(unmark(&s)),
+        }
+    }
 }
 
 impl std::error::Error for TimeError {}
@@ -947,16 +2518,316 @@ fn specific(m: &Match) -> bool {
     m.has("specific_day") || m.has("specific_period") || m.has("specific_time")
 }
 
+// the grammar fixes one order for the parts of a numeric date, but the configuration may
+// prefer another, so we reinterpret the parts
 fn n_date(date: &Match, config: &Config) -> Result<NaiveDate, TimeError> {
-    let year = year(date, config);
-    let month = n_month(date);
-    let day = n_day(date);
-    match NaiveDate::from_ymd_opt(year, month, day) {
+    let date = date.name("n_date").unwrap();
+    let parts = date
+        .children()
+        .unwrap()
+        .iter()
+        .map(|m| m.as_str())
+        .collect::<Vec<_>>();
+    for order in &config.date_orders {
+        if let Some((year, month, day)) = order.ymd(&parts, config) {
+            return match NaiveDate::from_ymd_opt(year, month, day) {
+                None => Err(TimeError::ImpossibleDate(format!(
+                    "cannot construct date with year {}, month {}, and day {}",
+                    year, month, day
+                ))),
+                Some(d) => Ok(d),
+            };
+        }
+    }
+    Err(TimeError::Parse(format!(
+        "{} is not a date in any of the orders {:?}",
+        date.as_str(),
+        config.date_orders
+    )))
+}
+
+// the range of an ISO 8601 interval: start/end, start/duration, duration/end, or a duration
+// alone, which extends into the past or future from now according to the configuration
+fn iso_interval(m: &Match, config: &Config) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
+    let s = m.as_str();
+    let is_duration = |e: &str| e.starts_with(['P', 'p']);
+    let (first, last) = match s.split_once('/') {
+        Some(endpoints) => endpoints,
+        None => {
+            let duration = iso_duration(s)?;
+            return if config.default_to_past {
+                Ok((duration.before(config.now)?, config.now))
+            } else {
+                Ok((config.now, duration.after(config.now)?))
+            };
+        }
+    };
+    let (d1, d2) = match (is_duration(first), is_duration(last)) {
+        (true, true) => {
+            return Err(TimeError::Parse(format!(
+                "the interval {} has neither a start nor an end",
+                s
+            )))
+        }
+        (false, true) => {
+            let d1 = iso_endpoint(first)?;
+            (d1, iso_duration(last)?.after(d1)?)
+        }
+        (true, false) => {
+            let d2 = iso_endpoint(last)?;
+            (iso_duration(first)?.before(d2)?, d2)
+        }
+        (false, false) => (iso_endpoint(first)?, iso_endpoint(last)?),
+    };
+    if d1 <= d2 {
+        Ok((d1, d2))
+    } else {
+        Err(TimeError::Misordered(format!(
+            "{} is after {}",
+            first, last
+        )))
+    }
+}
+
+// the first moment of a date, date time, or month in an ISO 8601 interval
+fn iso_endpoint(s: &str) -> Result<NaiveDateTime, TimeError> {
+    if s.contains(['T', 't']) {
+        let t = iso_date_time(s)?;
+        return Ok(t + Duration::milliseconds(milliseconds(s).unwrap_or(0)));
+    }
+    let date = s.replace('-', "");
+    let d = if date.len() == 6 {
+        NaiveDate::from_ymd_opt(date[0..4].parse::<i32>().unwrap(), s_to_n(&date[4..]), 1).unwrap()
+    } else {
+        iso_day(&date.to_uppercase())?
+    };
+    Ok(d.and_hms_opt(0, 0, 0).unwrap())
+}
+
+/// A length of time as returned by `parse_duration`. Months and years have no fixed length,
+/// so these are kept as a count of calendar months which only becomes a fixed length when
+/// the duration is applied to some moment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarDuration {
+    months: i64,
+    duration: Duration,
+}
+
+impl CalendarDuration {
+    /// The number of calendar months in the duration, a year being 12.
+    pub fn months(&self) -> i64 {
+        self.months
+    }
+    /// The portion of the duration with a fixed length: weeks, days, and smaller units.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+    /// The duration as a fixed length, if it has no months or years.
+    pub fn fixed(&self) -> Option<Duration> {
+        if self.months == 0 {
+            Some(self.duration)
+        } else {
+            None
+        }
+    }
+    /// The moment this long after the given moment. Months are added first, with the day
+    /// of the month clamped to the length of the month, so one month after January 31 is
+    /// the last day of February.
+    ///
+    /// Returns `TimeError::ImpossibleDate` if the moment would fall outside the representable
+    /// range.
+    pub fn after(&self, t: NaiveDateTime) -> Result<NaiveDateTime, TimeError> {
+        checked_add_months(t, self.months)
+            .and_then(|t| t.checked_add_signed(self.duration))
+            .ok_or_else(|| self.out_of_range(t, "after"))
+    }
+    /// The moment this long before the given moment.
+    ///
+    /// Returns `TimeError::ImpossibleDate` if the moment would fall outside the representable
+    /// range.
+    pub fn before(&self, t: NaiveDateTime) -> Result<NaiveDateTime, TimeError> {
+        self.months
+            .checked_neg()
+            .and_then(|months| checked_add_months(t, months))
+            .and_then(|t| t.checked_sub_signed(self.duration))
+            .ok_or_else(|| self.out_of_range(t, "before"))
+    }
+    fn out_of_range(&self, t: NaiveDateTime, direction: &str) -> TimeError {
+        TimeError::ImpossibleDate(format!(
+            "{} months and {} seconds {} {} is out of range",
+            self.months,
+            self.duration.num_seconds(),
+            direction,
+            t
+        ))
+    }
+}
+
+// interpret an ISO 8601 duration such as P1Y2M10DT2H30M
+fn iso_duration(s: &str) -> Result<CalendarDuration, TimeError> {
+    let mut months = 0.0;
+    let mut milliseconds = 0.0;
+    let mut in_time = false;
+    let mut number = String::new();
+    let mut empty = true;
+    for c in s[1..].chars() {
+        match c.to_ascii_uppercase() {
+            'T' => in_time = true,
+            '.' | ',' => number.push('.'),
+            c if c.is_ascii_digit() => number.push(c),
+            unit => {
+                let n = number.parse::<f64>().unwrap();
+                number.clear();
+                empty = false;
+                let seconds = match (unit, in_time) {
+                    ('Y', false) | ('M', false) => {
+                        if n.fract() != 0.0 {
+                            return Err(TimeError::Parse(format!(
+                                "{} has a fractional number of months or years",
+                                s
+                            )));
+                        }
+                        months += n * if unit == 'Y' { 12.0 } else { 1.0 };
+                        continue;
+                    }
+                    ('W', _) => 7 * 24 * 60 * 60,
+                    ('D', _) => 24 * 60 * 60,
+                    ('H', _) => 60 * 60,
+                    ('M', _) => 60,
+                    _ => 1,
+                };
+                milliseconds += n * seconds as f64 * 1000.0;
+            }
+        }
+    }
+    if empty {
+        return Err(TimeError::Parse(format!("{} is an empty duration", s)));
+    }
+    // beyond these no moment can be reached by the duration
+    if months > i32::MAX as f64 || milliseconds > i64::MAX as f64 {
+        return Err(TimeError::ImpossibleDate(format!("{} is too long", s)));
+    }
+    Ok(CalendarDuration {
+        months: months as i64,
+        duration: Duration::milliseconds(milliseconds.round() as i64),
+    })
+}
+
+// the date of a precise time, which may be numeric or an ISO 8601 week or ordinal date
+fn precise_date(m: &Match, config: &Config) -> Result<NaiveDate, TimeError> {
+    if m.has("n_date") {
+        n_date(m, config)
+    } else {
+        iso_date(m.name("iso_date").unwrap())
+    }
+}
+
+// an ISO 8601 week date such as 2023-W18-6 or ordinal date such as 2023-126
+fn iso_date(m: &Match) -> Result<NaiveDate, TimeError> {
+    iso_day(&m.as_str().to_uppercase().replace('-', ""))
+}
+
+// an ISO 8601 calendar, week, or ordinal date in the basic format, 20230506, 2023W186, or
+// 2023126
+fn iso_day(date: &str) -> Result<NaiveDate, TimeError> {
+    let year = date[0..4].parse::<i32>().unwrap();
+    if date.contains('W') {
+        week_date(year, s_to_n(&date[5..7]), s_to_n(&date[7..]))
+    } else if date.len() == 7 {
+        ordinal_date(year, s_to_n(&date[4..]))
+    } else {
+        let (month, day) = (s_to_n(&date[4..6]), s_to_n(&date[6..]));
+        NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| {
+            TimeError::ImpossibleDate(format!(
+                "cannot construct date with year {}, month {}, and day {}",
+                year, month, day
+            ))
+        })
+    }
+}
+
+// the date of a day of an ISO 8601 week, Monday being day 1
+fn week_date(year: i32, week: u32, day: u32) -> Result<NaiveDate, TimeError> {
+    match NaiveDate::from_isoywd_opt(year, week, Weekday::Mon) {
+        Some(d) => Ok(d + Duration::days(day as i64 - 1)),
         None => Err(TimeError::ImpossibleDate(format!(
-            "cannot construct date with year {}, month {}, and day {}",
-            year, month, day
+            "there is no week {} in {}",
+            week, year
         ))),
-        Some(d) => Ok(d),
+    }
+}
+
+fn ordinal_date(year: i32, day: u32) -> Result<NaiveDate, TimeError> {
+    NaiveDate::from_yo_opt(year, day)
+        .ok_or_else(|| TimeError::ImpossibleDate(format!("there is no day {} in {}", day, year)))
+}
+
+// the moment named by an ISO 8601 date and time such as 2023-05-06T12:03:05, less any
+// fractional second or offset, which are handled by second_span and expression_zone
+fn iso_date_time(s: &str) -> Result<NaiveDateTime, TimeError> {
+    let s = s.to_uppercase();
+    let (date, time) = s.split_at(s.find('T').unwrap());
+    let d = iso_day(&date.replace('-', ""))?;
+    let time = time[1..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == ':')
+        .filter(|c| *c != ':')
+        .collect::<String>();
+    let hour = s_to_n(&time[0..2]);
+    let minute = if time.len() > 2 {
+        s_to_n(&time[2..4])
+    } else {
+        0
+    };
+    let second = if time.len() > 4 {
+        s_to_n(&time[4..])
+    } else {
+        0
+    };
+    if hour == 24 {
+        if minute > 0 || second > 0 {
+            return Err(TimeError::ImpossibleDate(format!(
+                "{} is past the end of the day",
+                s
+            )));
+        }
+        // 24:00 is the end of the day, which is the start of the next
+        return Ok(d.succ_opt().unwrap().and_hms_opt(0, 0, 0).unwrap());
+    }
+    Ok(d.and_hms_opt(hour, minute, second).unwrap())
+}
+
+// the parts of a numeric date, which may not have been parsed as intended, so they're
+// validated here
+fn year_part(s: &str, config: &Config) -> Option<i32> {
+    lazy_static! {
+        static ref YEAR: Regex =
+            Regex::new(r"\A(?:'(\d\d)|(\d\d)|(-?)([1-9]\d{0,4}|0))\z").unwrap();
+    }
+    let cap = YEAR.captures(s)?;
+    if let Some(y) = cap.get(1).or_else(|| cap.get(2)) {
+        Some(short_year(s_to_n(y.as_str()) as i32, config))
+    } else {
+        let y = s_to_n(&cap[4]) as i32;
+        Some(if cap[3].is_empty() { y } else { -y })
+    }
+}
+
+fn month_part(s: &str) -> Option<u32> {
+    let m = s.parse::<u32>().ok()?;
+    if s.len() <= 2 && (1..=12).contains(&m) {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+fn day_part(s: &str) -> Option<u32> {
+    let d = s.parse::<u32>().ok()?;
+    if s.len() <= 2 && (1..=31).contains(&d) {
+        Some(d)
+    } else {
+        None
     }
 }
 
@@ -964,7 +2835,7 @@ fn handle_specific_day(
     m: &Match,
     config: &Config,
 ) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
-    let now = config.now.clone();
+    let now = config.now;
     let mut times = m.all_names("time");
     if times.len() > 1 {
         return Err(TimeError::Parse(format!(
@@ -972,7 +2843,7 @@ fn handle_specific_day(
             m.as_str()
         )));
     }
-    let time = times.pop();
+    let time = times.pop().or_else(|| m.name("day_part"));
     if let Some(adverb) = m.name("adverb") {
         return match adverb.as_str().chars().nth(0).expect("empty string") {
             // now
@@ -982,33 +2853,51 @@ fn handle_specific_day(
                 'd' | 'D' => Ok(moment_and_time(&config.period(Period::Day), time)),
                 // tomorrow
                 'm' | 'M' => Ok(moment_and_time(
-                    &Config::new()
-                        .now(now + Duration::days(1))
-                        .period(Period::Day),
+                    &config.now(now + Duration::days(1)).period(Period::Day),
                     time,
                 )),
+                // tonight
+                'n' | 'N' => Ok(if time.is_some() {
+                    moment_and_time(&config.period(Period::Day), time)
+                } else {
+                    day_part_span(now.date(), DayPart::Night, config)
+                }),
                 _ => unreachable!(),
             },
             // yesterday
             'y' | 'Y' => Ok(moment_and_time(
-                &Config::new()
-                    .now(now - Duration::days(1))
-                    .period(Period::Day),
+                &config.now(now - Duration::days(1)).period(Period::Day),
                 time,
             )),
             _ => unreachable!(),
         };
     }
+    if let Some(modified_day) = m.name("modified_day") {
+        let d = modified_weekday(modified_day, config);
+        return Ok(moment_and_time(
+            &config
+                .now(d.and_hms_opt(0, 0, 0).unwrap())
+                .period(Period::Day),
+            time,
+        ));
+    }
     if let Some(date) = m.name("date_with_year") {
+        if let Some(date) = date.name("iso_date") {
+            let d1 = iso_date(date)?.and_hms_opt(0, 0, 0).unwrap();
+            return Ok(moment_and_time(&config.now(d1).period(Period::Day), time));
+        }
+        if let Some(date) = date.name("holiday_date") {
+            let d1 = holiday(date, year(date, config), config)?
+                .and_hms_opt(0, 0, 0)
+                .unwrap();
+            return Ok(moment_and_time(&config.now(d1).period(Period::Day), time));
+        }
         if let Some(date) = date.name("n_date") {
             return match n_date(date, config) {
                 Err(s) => Err(s),
                 Ok(d1) => {
                     let d1 = d1.and_hms_opt(0, 0, 0).unwrap();
-                    Ok(moment_and_time(
-                        &Config::new().now(d1).period(Period::Day),
-                        time,
-                    ))
+                    Ok(moment_and_time(&config.now(d1).period(Period::Day), time))
                 }
             };
         }
@@ -1031,10 +2920,7 @@ fn handle_specific_day(
                         let wd = weekday(wd.as_str());
                         if wd == d1.weekday() {
                             let d1 = d1.and_hms_opt(0, 0, 0).unwrap();
-                            Ok(moment_and_time(
-                                &Config::new().now(d1).period(Period::Day),
-                                time,
-                            ))
+                            Ok(moment_and_time(&config.now(d1).period(Period::Day), time))
                         } else {
                             Err(TimeError::Weekday(format!(
                                 "the weekday of year {}, month {}, day {} is not {}",
@@ -1046,10 +2932,7 @@ fn handle_specific_day(
                         }
                     } else {
                         let d1 = d1.and_hms_opt(0, 0, 0).unwrap();
-                        Ok(moment_and_time(
-                            &Config::new().now(d1).period(Period::Day),
-                            time,
-                        ))
+                        Ok(moment_and_time(&config.now(d1).period(Period::Day), time))
                     }
                 }
             };
@@ -1063,24 +2946,28 @@ fn handle_specific_period(
     moment: &Match,
     config: &Config,
 ) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
+    if let Some(window) = moment.name("rolling_window") {
+        return rolling_window(window, config);
+    }
     if let Some(moment) = moment.name("relative_period") {
-        let count = count(moment.name("count").unwrap()) as i64;
-        let (displacement, period) = match moment
-            .name("displacement")
-            .unwrap()
-            .as_str()
-            .chars()
-            .nth(0)
-            .unwrap()
-        {
-            'w' | 'W' => (Duration::weeks(count), Period::Week),
-            'd' | 'D' => (Duration::days(count), Period::Day),
-            'h' | 'H' => (Duration::hours(count), Period::Hour),
-            'm' | 'M' => (Duration::minutes(count), Period::Minute),
-            's' | 'S' => (Duration::seconds(count), Period::Second),
-            _ => unreachable!(),
-        };
-        let d = match moment
+        let count = count(moment.name("count").unwrap())? as i64;
+        if let Some(unit) = moment.name("business_unit") {
+            let n = if moment
+                .name("from_now_or_ago")
+                .unwrap()
+                .as_str()
+                .to_lowercase()
+                == "ago"
+            {
+                -count
+            } else {
+                count
+            };
+            let d = nth_business_day(config.now.date(), n, weekdays_only(unit.as_str()), config);
+            return Ok(whole_day(d));
+        }
+        let displacement = moment.name("displacement").unwrap().as_str();
+        let count = match moment
             .name("from_now_or_ago")
             .unwrap()
             .as_str()
@@ -1088,18 +2975,27 @@ fn handle_specific_period(
             .nth(0)
             .unwrap()
         {
-            'a' | 'A' => config.now - displacement,
-            'f' | 'F' => config.now + displacement,
+            'a' | 'A' => -count,
+            'f' | 'F' => count,
             _ => unreachable!(),
         };
-        let span = match period {
-            Period::Week => (d, d + Duration::weeks(1)),
-            _ => moment_to_period(d, &period, config),
-        };
-        return Ok(span);
+        return displaced_period(count, displacement, config);
+    }
+    if let Some(period) = moment.name("iso_period") {
+        let s = period.as_str().to_uppercase().replace('-', "");
+        if s.contains('W') {
+            let d = week_date(s[0..4].parse::<i32>().unwrap(), s_to_n(&s[5..]), 1)?;
+            let d = d.and_hms_opt(0, 0, 0).unwrap();
+            return Ok((d, d + Duration::weeks(1)));
+        }
+        let d = NaiveDate::from_ymd_opt(s[0..4].parse::<i32>().unwrap(), s_to_n(&s[4..]), 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        return Ok(moment_to_period(d, &Period::Month, config));
     }
     if let Some(moment) = moment.name("month_and_year") {
-        let y = year(moment, &config);
+        let y = year(moment, config);
         let m = a_month(moment);
         return match NaiveDate::from_ymd_opt(y, m, 1) {
             None => unreachable!(),
@@ -1124,23 +3020,40 @@ fn handle_specific_period(
             };
             return Ok(moment_to_period(d, &Period::Month, config));
         }
-        if let Some(wd) = moment.name("a_day") {
-            let wd = weekday(wd.as_str());
-            let offset = config.now.weekday().num_days_from_monday() as i64
-                - wd.num_days_from_monday() as i64;
-            let d = config.now.date() - Duration::days(offset);
-            let d = match modifier {
-                PeriodModifier::Next => d + Duration::days(7),
-                PeriodModifier::Last => d - Duration::days(7),
-                PeriodModifier::This => d,
-            };
+        if moment.has("a_day") {
+            let d = modified_weekday(moment, config);
             return Ok(moment_to_period(
                 d.and_hms_opt(0, 0, 0).unwrap(),
                 &Period::Day,
                 config,
             ));
         }
-        return match ModifiablePeriod::from_match(moment.name("modifiable_period").unwrap()) {
+        if let Some(part) = moment.name("day_part") {
+            let d = match modifier {
+                PeriodModifier::Next => config.now.date() + Duration::days(1),
+                PeriodModifier::Last => config.now.date() - Duration::days(1),
+                PeriodModifier::This => config.now.date(),
+            };
+            return Ok(day_part_span(d, DayPart::from_match(part), config));
+        }
+        if let Some(unit) = moment.name("business_day") {
+            let weekdays_only = weekdays_only(unit.as_str());
+            let today = config.now.date();
+            let d = match modifier {
+                PeriodModifier::Next => nth_business_day(today, 1, weekdays_only, config),
+                PeriodModifier::Last => nth_business_day(today, -1, weekdays_only, config),
+                PeriodModifier::This => {
+                    if config.business_day(today, weekdays_only) {
+                        today
+                    } else {
+                        nth_business_day(today, 1, weekdays_only, config)
+                    }
+                }
+            };
+            return Ok(whole_day(d));
+        }
+        let period = ModifiablePeriod::from_match(moment.name("modifiable_period").unwrap());
+        return match period {
             ModifiablePeriod::Week => {
                 let (d, _) = moment_to_period(config.now, &Period::Week, config);
                 let d = match modifier {
@@ -1191,6 +3104,22 @@ fn handle_specific_period(
                 };
                 Ok(moment_to_period(d, &Period::Year, config))
             }
+            ModifiablePeriod::Quarter
+            | ModifiablePeriod::FiscalQuarter
+            | ModifiablePeriod::FiscalYear => {
+                let (period, months) = match period {
+                    ModifiablePeriod::Quarter => (Period::Quarter, 3),
+                    ModifiablePeriod::FiscalQuarter => (Period::FiscalQuarter, 3),
+                    _ => (Period::FiscalYear, 12),
+                };
+                let (d, _) = moment_to_period(config.now, &period, config);
+                let d = match modifier {
+                    PeriodModifier::Next => add_months(d, months),
+                    PeriodModifier::Last => add_months(d, -months),
+                    PeriodModifier::This => d,
+                };
+                Ok(moment_to_period(d, &period, config))
+            }
             ModifiablePeriod::PayPeriod => {
                 if config.pay_period_start.is_some() {
                     let (d, _) = moment_to_period(config.now, &Period::PayPeriod, config);
@@ -1208,6 +3137,52 @@ fn handle_specific_period(
             }
         };
     }
+    if let Some(moment) = moment.name("named_quarter") {
+        let q = moment
+            .name("q_number")
+            .unwrap()
+            .as_str()
+            .chars()
+            .nth(1)
+            .unwrap()
+            .to_digit(10)
+            .unwrap() as i64;
+        return if let Some(fy) = moment.name("fy") {
+            let d = add_months(
+                fiscal_year_start(fiscal_year(fy, config), config),
+                3 * (q - 1),
+            );
+            Ok(moment_to_period(d, &Period::FiscalQuarter, config))
+        } else if moment.has("fiscal") {
+            let start = if moment.has("year") {
+                fiscal_year_start(year(moment, config), config)
+            } else {
+                moment_to_period(config.now, &Period::FiscalYear, config).0
+            };
+            let d = add_months(start, 3 * (q - 1));
+            Ok(moment_to_period(d, &Period::FiscalQuarter, config))
+        } else {
+            let y = if moment.has("year") {
+                year(moment, config)
+            } else {
+                config.now.year()
+            };
+            let d = first_moment_of_day(y, 3 * q as u32 - 2, 1);
+            Ok(moment_to_period(d, &Period::Quarter, config))
+        };
+    }
+    if let Some(moment) = moment.name("fiscal_year") {
+        let y = if let Some(fy) = moment.name("fy") {
+            fiscal_year(fy, config)
+        } else {
+            year(moment, config)
+        };
+        return Ok(moment_to_period(
+            fiscal_year_start(y, config),
+            &Period::FiscalYear,
+            config,
+        ));
+    }
     if let Some(moment) = moment.name("year") {
         let year = year(moment, config);
         return Ok(moment_to_period(
@@ -1219,12 +3194,130 @@ fn handle_specific_period(
     unreachable!()
 }
 
+// the period of the given unit some number of units from now: "2 weeks ago", "6 months from now"
+fn displaced_period(
+    count: i64,
+    unit: &str,
+    config: &Config,
+) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
+    let d = shift(config.now, count, unit)?;
+    Ok(match unit_period(unit) {
+        Period::Week => (d, shift(d, 1, unit)?),
+        period => moment_to_period(d, &period, config),
+    })
+}
+
+// a window of some number of units ending or beginning now: "the last 7 days", "the next 3 months"
+fn rolling_window(m: &Match, config: &Config) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
+    let mut n = m.name("count").map_or(Ok(1), count)? as i64;
+    // next, coming, and following windows are in the future
+    let future = matches!(
+        m.name("window").unwrap().as_str().chars().next().unwrap(),
+        'n' | 'N' | 'c' | 'C' | 'f' | 'F'
+    );
+    if let Some(unit) = m.name("business_unit") {
+        let weekdays_only = weekdays_only(unit.as_str());
+        let today = config.now.date();
+        let (first, last) = if future { (1, n) } else { (-n, -1) };
+        return Ok((
+            whole_day(nth_business_day(today, first, weekdays_only, config)).0,
+            whole_day(nth_business_day(today, last, weekdays_only, config)).1,
+        ));
+    }
+    let unit = if m.has("fortnight") {
+        n *= 2;
+        "weeks"
+    } else {
+        m.name("unit").unwrap().as_str()
+    };
+    let (d1, d2) = if config.whole_windows {
+        moment_to_period(config.now, &unit_period(unit), config)
+    } else {
+        (config.now, config.now)
+    };
+    Ok(if future {
+        (d2, shift(d2, n, unit)?)
+    } else {
+        (shift(d1, -n, unit)?, d1)
+    })
+}
+
+// the year named by an expression such as "FY2024" or "FY 24"
+fn fiscal_year(fy: &Match, config: &Config) -> i32 {
+    if fy.has("year") {
+        year(fy, config)
+    } else {
+        let digits = &fy.as_str()[2..];
+        let y = s_to_n(digits) as i32;
+        if digits.len() == 2 {
+            short_year(y, config)
+        } else {
+            y
+        }
+    }
+}
+
+// the first moment of a fiscal year
+fn fiscal_year_start(year: i32, config: &Config) -> NaiveDateTime {
+    if config.fiscal_year_start == 1 {
+        first_moment_of_day(year, 1, 1)
+    } else {
+        first_moment_of_day(year - 1, config.fiscal_year_start, 1)
+    }
+}
+
+// the day named by a weekday with a modifier, such as "next Tuesday"
+fn modified_weekday(m: &Match, config: &Config) -> NaiveDate {
+    let wd = weekday(m.name("a_day").unwrap().as_str());
+    let offset =
+        config.now.weekday().num_days_from_monday() as i64 - wd.num_days_from_monday() as i64;
+    let d = config.now.date() - Duration::days(offset);
+    if let Some(beyond) = m.name("beyond") {
+        // Tuesday after next is the Tuesday after next Tuesday
+        return match beyond.as_str().chars().next().unwrap() {
+            'a' | 'A' => d + Duration::days(14),
+            _ => d - Duration::days(14),
+        };
+    }
+    let d = match m.name("modifier") {
+        // "Friday week" is a week after the coming Friday
+        None if d <= config.now.date() => d + Duration::days(7),
+        modifier => match PeriodModifier::from_match(modifier) {
+            PeriodModifier::Next => d + Duration::days(7),
+            PeriodModifier::Last => d - Duration::days(7),
+            PeriodModifier::This => d,
+        },
+    };
+    if m.has("week_hence") {
+        d + Duration::days(7)
+    } else {
+        d
+    }
+}
+
+// the span of a part of a day; a part which ends at an hour no later than it begins ends on
+// the following day
+fn day_part_span(d: NaiveDate, part: DayPart, config: &Config) -> (NaiveDateTime, NaiveDateTime) {
+    let (start, end) = config.day_parts[part as usize];
+    let d = d.and_hms_opt(0, 0, 0).unwrap();
+    let d1 = d + Duration::hours(start as i64);
+    let d2 = if end > start {
+        d + Duration::hours(end as i64)
+    } else {
+        d + Duration::days(1) + Duration::hours(end as i64)
+    };
+    (d1, d2)
+}
+
 enum ModifiablePeriod {
     Week,
     Month,
     Year,
     PayPeriod,
     Weekend,
+    Quarter,
+    FiscalQuarter,
+    FiscalYear,
 }
 
 impl ModifiablePeriod {
@@ -1240,6 +3333,14 @@ impl ModifiablePeriod {
             'm' | 'M' => ModifiablePeriod::Month,
             'y' | 'Y' => ModifiablePeriod::Year,
             'p' | 'P' => ModifiablePeriod::PayPeriod,
+            'q' | 'Q' => ModifiablePeriod::Quarter,
+            'f' | 'F' => {
+                if m.as_str().to_lowercase().ends_with("year") {
+                    ModifiablePeriod::FiscalYear
+                } else {
+                    ModifiablePeriod::FiscalQuarter
+                }
+            }
             _ => unreachable!(),
         }
     }
@@ -1271,20 +3372,103 @@ fn handle_specific_time(
     config: &Config,
 ) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
     if let Some(moment) = moment.name("precise_time") {
-        return match n_date(moment, config) {
+        if let Some(t) = moment.name("iso_date_time") {
+            return Ok(second_span(iso_date_time(t.as_str())?, moment, config));
+        }
+        return match precise_date(moment, config) {
             Err(s) => Err(s),
             Ok(d) => {
                 let (hour, minute, second, _) = time(moment);
                 let m = d.and_hms_opt(hour, minute, second).unwrap();
-                Ok(moment_to_period(m, &Period::Second, config))
+                Ok(second_span(m, moment, config))
             }
         };
     }
-    return if moment.has("first_time") {
+    if moment.has("anchor") {
+        return anchor(moment, config);
+    }
+    if moment.has("first_time") {
         Ok(moment_to_period(first_moment(), &config.period, config))
     } else {
         Ok((last_moment(), last_moment()))
+    }
+}
+
+// the nth or last day of some kind within a period: the second Tuesday in March 2024, the last
+// business day of the quarter
+fn nth_day(
+    nth: &Match,
+    period: &Match,
+    config: &Config,
+) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
+    let (d1, d2) = handle_specific_period(period, config)?;
+    // the, the ordinal, the kind of day, and of or in
+    let words = nth.as_str().split_whitespace().collect::<Vec<_>>();
+    let (n, kind) = (words[1], words[2..words.len() - 1].join(" "));
+    let wanted = |d: &NaiveDate| match kind.to_lowercase().as_str() {
+        "day" => true,
+        "business day" | "working day" | "workday" | "weekday" => {
+            config.business_day(*d, weekdays_only(&kind))
+        }
+        _ => d.weekday() == weekday(&kind),
+    };
+    let (first, last) = (d1.date(), last_day(d1, d2));
+    let d = if n.eq_ignore_ascii_case("last") {
+        last.iter_days()
+            .rev()
+            .take_while(|d| *d >= first)
+            .find(|d| wanted(d))
+    } else {
+        let i = if n.starts_with(|c: char| c.is_ascii_digit()) {
+            n[0..n.len() - 2]
+                .parse::<usize>()
+                .map_err(|_| TimeError::ImpossibleDate(format!("{} is too large an ordinal", n)))?
+        } else {
+            ordinal(n) as usize
+        };
+        first
+            .iter_days()
+            .take_while(|d| *d <= last)
+            .filter(|d| wanted(d))
+            .nth(i - 1)
+    };
+    match d {
+        Some(d) => Ok(whole_day(d)),
+        None => Err(TimeError::ImpossibleDate(format!(
+            "there is no {} {} in {}",
+            n,
+            kind,
+            period.as_str()
+        ))),
+    }
+}
+
+// the first, middle, or last third of a range, as named by "the beginning of", "mid-", "late",
+// and so forth; a range of at least three whole days is divided into whole days
+fn part_of(part: &Match, d1: NaiveDateTime, d2: NaiveDateTime) -> (NaiveDateTime, NaiveDateTime) {
+    let i = if part.has("beginning") {
+        0
+    } else if part.has("middle") {
+        1
+    } else if part.has("end") {
+        2
+    } else {
+        // early, mid, or late
+        match part.as_str().chars().next().expect("empty string") {
+            'e' | 'E' => 0,
+            'm' | 'M' => 1,
+            _ => 2,
+        }
     };
+    let days = (d2 - d1).num_days();
+    let boundary = |i: i64| {
+        if days >= 3 && d1 == whole_day(d1.date()).0 && d1 + Duration::days(days) == d2 {
+            d1 + Duration::days(days * i / 3)
+        } else {
+            d1 + (d2 - d1) * i as i32 / 3
+        }
+    };
+    (boundary(i), boundary(i + 1))
 }
 
 fn handle_one_time(
@@ -1293,12 +3477,16 @@ fn handle_one_time(
 ) -> Result<(NaiveDateTime, NaiveDateTime, bool), TimeError> {
     let r = if moment.has("specific_day") {
         handle_specific_day(moment, config)
-    } else if let Some(moment) = moment.name("specific_period") {
-        handle_specific_period(moment, config)
+    } else if moment.has("specific_period") {
+        specific_moment(moment, config)
     } else if let Some(moment) = moment.name("specific_time") {
         handle_specific_time(moment, config)
     } else {
-        relative_moment(moment, config, &config.now, config.default_to_past)
+        // "two weeks from Friday" counts from the coming Friday
+        let from = moment
+            .name("direction")
+            .is_some_and(|d| d.as_str().eq_ignore_ascii_case("from"));
+        relative_moment(moment, config, &config.now, config.default_to_past && !from)
     };
     match r {
         Ok((d1, d2)) => Ok((d1, d2, false)),
@@ -1308,7 +3496,9 @@ fn handle_one_time(
 
 // add time to a date
 fn moment_and_time(config: &Config, daytime: Option<&Match>) -> (NaiveDateTime, NaiveDateTime) {
-    if let Some(daytime) = daytime {
+    if let Some(part) = daytime.filter(|m| m.rule() == "day_part") {
+        day_part_span(config.now.date(), DayPart::from_match(part), config)
+    } else if let Some(daytime) = daytime {
         let (hour, minute, second, is_midnight) = time(daytime);
         let mut m = config
             .now
@@ -1321,12 +3511,38 @@ fn moment_and_time(config: &Config, daytime: Option<&Match>) -> (NaiveDateTime,
         if is_midnight {
             m = m + Duration::days(1); // midnight is second 0 *of the next day*
         }
-        moment_to_period(m, &Period::Second, config)
+        second_span(m, daytime, config)
     } else {
         moment_to_period(config.now, &config.period, config)
     }
 }
 
+// the span of a moment given to the second or, if it has a fractional second, to the
+// millisecond
+fn second_span(t: NaiveDateTime, m: &Match, config: &Config) -> (NaiveDateTime, NaiveDateTime) {
+    let s = m
+        .name("iso_date_time")
+        .or_else(|| m.name("second"))
+        .map(|s| s.as_str())
+        .unwrap_or("");
+    if let Some(ms) = milliseconds(s) {
+        let t = t + Duration::milliseconds(ms);
+        (t, t + Duration::milliseconds(1))
+    } else {
+        moment_to_period(t, &Period::Second, config)
+    }
+}
+
+// the milliseconds in the fractional second of a time such as 12:03:05.123, if it has one
+fn milliseconds(s: &str) -> Option<i64> {
+    let i = s.find(['.', ','])?;
+    let digits = s[i + 1..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>();
+    Some(format!("{:0<3}", digits)[0..3].parse::<i64>().unwrap())
+}
+
 fn relative_moment(
     m: &Match,
     config: &Config,
@@ -1339,7 +3555,7 @@ fn relative_moment(
                 &config
                     .now(d.and_hms_opt(0, 0, 0).unwrap())
                     .period(Period::Day),
-                m.name("time"),
+                m.name("time").or_else(|| m.name("day_part")),
             )),
             Err(e) => Err(e),
         };
@@ -1359,7 +3575,7 @@ fn relative_moment(
             &config
                 .now(d.and_hms_opt(0, 0, 0).unwrap())
                 .period(Period::Day),
-            m.name("time"),
+            m.name("time").or_else(|| m.name("day_part")),
         ));
     }
     if let Some(t) = m.name("time") {
@@ -1427,11 +3643,7 @@ fn month_and_a_day(
         let mut year = config.now.year();
         let mut month = other_time.month();
         let day = o_day(m, month);
-        let wd = if let Some(a_day) = m.name("a_day") {
-            Some(weekday(a_day.as_str()))
-        } else {
-            None
-        };
+        let wd = m.name("a_day").map(|a_day| weekday(a_day.as_str()));
         // search backwards through the calendar for a possible day
         for _ in 0..4 * 7 * 12 {
             if let Some(d) = NaiveDate::from_ymd_opt(year, month, day) {
@@ -1452,10 +3664,20 @@ fn month_and_a_day(
             config.now.year()
         )));
     }
-    let (month, day) = if let Some(month) = m.name("n_month") {
-        let month = n_month(month);
-        let day = m.name("n_day").unwrap();
-        (month, n_day(day))
+    if m.has("holiday") {
+        return if before {
+            holiday(m, config.now.year(), config)
+        } else {
+            let d = holiday(m, other_time.year(), config)?;
+            if d < other_time.date() {
+                holiday(m, other_time.year() + 1, config)
+            } else {
+                Ok(d)
+            }
+        };
+    }
+    let (month, day) = if m.has("n_month") {
+        n_day_and_month(m.name("day_and_month").unwrap(), config)?
     } else {
         let month = a_month(m);
         let day = if let Some(day) = m.name("n_day") {
@@ -1493,8 +3715,15 @@ fn specific_moment(
     if m.has("specific_day") {
         return handle_specific_day(m, config);
     }
-    if let Some(m) = m.name("specific_period") {
-        return handle_specific_period(m, config);
+    if let Some(period) = m.name("specific_period") {
+        if let Some(nth) = m.name("nth_of") {
+            return nth_day(nth, period, config);
+        }
+        let (d1, d2) = handle_specific_period(period, config)?;
+        return Ok(match m.name("sub_period") {
+            Some(part) => part_of(part, d1, d2),
+            None => (d1, d2),
+        });
     }
     if let Some(m) = m.name("specific_time") {
         return handle_specific_time(m, config);
@@ -1502,6 +3731,36 @@ fn specific_moment(
     unreachable!()
 }
 
+// the range of a named anchor
+fn anchor(m: &Match, config: &Config) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
+    let name = normalize(&unmark(m.name("anchor").unwrap().as_str()));
+    match config.anchors.iter().find(|(n, _, _)| normalize(n) == name) {
+        Some((_, start, end)) => Ok((*start, *end)),
+        None => Err(TimeError::Parse(format!(
+            "{} is not a known anchor",
+            m.name("anchor").unwrap().as_str()
+        ))),
+    }
+}
+
+// the date of a holiday in the given year
+fn holiday(m: &Match, year: i32, config: &Config) -> Result<NaiveDate, TimeError> {
+    let name = normalize(&unmark(m.name("holiday").unwrap().as_str()));
+    config
+        .holidays
+        .names()
+        .into_iter()
+        .find(|n| normalize(n) == name)
+        .and_then(|n| config.holidays.date(&n, year))
+        .ok_or_else(|| {
+            TimeError::ImpossibleDate(format!(
+                "{} does not occur in the year {}",
+                m.name("holiday").unwrap().as_str(),
+                year
+            ))
+        })
+}
+
 fn a_month(m: &Match) -> u32 {
     match m.name("a_month").unwrap().as_str()[0..3]
         .to_lowercase()
@@ -1532,6 +3791,9 @@ fn time(m: &Match) -> (u32, u32, u32, bool) {
             _ => (0, 0, 0, true),
         };
     }
+    if let Some(spoken) = m.name("spoken_time") {
+        return spoken_time(spoken.as_str());
+    }
     let hour = if let Some(hour_24) = m.name("hour_24") {
         let hour = s_to_n(hour_24.name("h24").unwrap().as_str());
         if hour == 24 {
@@ -1560,7 +3822,7 @@ fn time(m: &Match) -> (u32, u32, u32, bool) {
     if let Some(minute) = m.name("minute") {
         let minute = s_to_n(minute.as_str());
         if let Some(second) = m.name("second") {
-            let second = s_to_n(second.as_str());
+            let second = s_to_n(&second.as_str()[0..2]);
             (hour, minute, second, false)
         } else {
             (hour, minute, 0, false)
@@ -1570,36 +3832,100 @@ fn time(m: &Match) -> (u32, u32, u32, bool) {
     }
 }
 
-fn n_month(m: &Match) -> u32 {
-    lazy_static! {
-        static ref MONTH: Regex = Regex::new(r"\A0?(\d{1,2})\z").unwrap();
+// extract hour, minute, and second from a time such as "quarter to five" or "three PM"
+fn spoken_time(s: &str) -> (u32, u32, u32, bool) {
+    let mut s = s.to_lowercase();
+    let pm = s.ends_with("pm") || s.ends_with("p.m.");
+    if pm || s.ends_with("am") || s.ends_with("a.m.") {
+        s = s.trim_end_matches(|c: char| "amp.".contains(c)).to_string();
+    }
+    let s = s
+        .trim_end()
+        .trim_end_matches("o'clock")
+        .trim_end_matches("o’clock")
+        .trim_end_matches("oclock");
+    let words = s.split_whitespace().collect::<Vec<_>>();
+    let hour = words[words.len() - 1];
+    let mut hour = if hour.starts_with(|c: char| c.is_ascii_digit()) {
+        s_to_n(hour)
+    } else {
+        number_word(hour)
+    };
+    if pm {
+        hour = (hour + 12) % 24;
+    }
+    if let Some(i) = words
+        .iter()
+        .position(|w| ["past", "after", "to", "till", "before", "of"].contains(w))
+    {
+        let minutes = &words[..i];
+        let minutes = if minutes.contains(&"quarter") {
+            15
+        } else if minutes[0] == "half" {
+            30
+        } else if minutes[0].starts_with(|c: char| c.is_ascii_digit()) {
+            s_to_n(minutes[0].trim_end_matches(char::is_alphabetic))
+        } else {
+            number_word(
+                &minutes
+                    .iter()
+                    .filter(|w| !w.starts_with("minute"))
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+        };
+        return if words[i] == "past" || words[i] == "after" {
+            (hour, minutes, 0, false)
+        } else {
+            ((hour + 23) % 24, 60 - minutes, 0, false)
+        };
     }
-    let cap = MONTH.captures(m.name("n_month").unwrap().as_str()).unwrap();
-    cap[1].parse::<u32>().unwrap()
+    (hour, 0, 0, false)
 }
 
-fn year(m: &Match, config: &Config) -> i32 {
-    let year = m.name("year").unwrap();
-    if let Some(sy) = year.name("short_year") {
-        let y = s_to_n(sy.as_str()) as i32;
-        let this_year = config.now.year() % 100;
-        if config.default_to_past {
-            if this_year < y {
-                // previous century
-                config.now.year() - this_year - 100 + y
-            } else {
-                // this century
-                config.now.year() - this_year + y
+// the value of a spelled out number such as "twenty-five"
+fn number_word(s: &str) -> u32 {
+    let s = s.to_lowercase();
+    if s.starts_with("twenty") && s.len() > 6 {
+        return 25;
+    }
+    NUMBER_WORDS.iter().position(|&w| w == s).unwrap() as u32 + 1
+}
+
+// the month and day of a numeric date without a year, such as "5/6"; only the relative order
+// of the month and day in the configured date orders matters
+fn n_day_and_month(m: &Match, config: &Config) -> Result<(u32, u32), TimeError> {
+    let parts = m
+        .children()
+        .unwrap()
+        .iter()
+        .map(|m| m.as_str())
+        .collect::<Vec<_>>();
+    for order in &config.date_orders {
+        let month_and_day = match order {
+            DateOrder::YearMonthDay | DateOrder::MonthDayYear => {
+                month_part(parts[0]).zip(day_part(parts[1]))
             }
-        } else {
-            if this_year > y {
-                // next century
-                config.now.year() - this_year + 100 + y
-            } else {
-                // this century
-                config.now.year() - this_year + y
+            DateOrder::YearDayMonth | DateOrder::DayMonthYear => {
+                month_part(parts[1]).zip(day_part(parts[0]))
             }
+        };
+        if let Some(month_and_day) = month_and_day {
+            return Ok(month_and_day);
         }
+    }
+    Err(TimeError::Parse(format!(
+        "{} is not a date in any of the orders {:?}",
+        m.as_str(),
+        config.date_orders
+    )))
+}
+
+fn year(m: &Match, config: &Config) -> i32 {
+    let year = m.name("year").unwrap();
+    if let Some(sy) = year.name("short_year") {
+        short_year(s_to_n(sy.as_str()) as i32, config)
     } else if let Some(suffix) = year.name("year_suffix") {
         let y = s_to_n(year.name("suffix_year").unwrap().as_str()) as i32;
         if suffix.has("bce") {
@@ -1617,6 +3943,28 @@ fn year(m: &Match, config: &Config) -> i32 {
     }
 }
 
+// expand a two-digit year to the nearest appropriate full year
+fn short_year(y: i32, config: &Config) -> i32 {
+    let this_year = config.now.year() % 100;
+    if config.default_to_past {
+        if this_year < y {
+            // previous century
+            config.now.year() - this_year - 100 + y
+        } else {
+            // this century
+            config.now.year() - this_year + y
+        }
+    } else {
+        if this_year > y {
+            // next century
+            config.now.year() - this_year + 100 + y
+        } else {
+            // this century
+            config.now.year() - this_year + y
+        }
+    }
+}
+
 fn s_to_n(s: &str) -> u32 {
     lazy_static! {
         static ref S_TO_N: Regex = Regex::new(r"\A[\D0]*(\d+)\z").unwrap();
@@ -1813,6 +4161,9 @@ fn moment_to_period(
             );
             (d1, d1 + Duration::seconds(1))
         }
+        Period::Quarter => months_containing(now, 1, 3),
+        Period::FiscalQuarter => months_containing(now, config.fiscal_year_start, 3),
+        Period::FiscalYear => months_containing(now, config.fiscal_year_start, 12),
         Period::PayPeriod => {
             if let Some(pps) = config.pay_period_start {
                 // find the current pay period start
@@ -1833,18 +4184,296 @@ fn moment_to_period(
     }
 }
 
-#[derive(Debug, Clone)]
-enum Period {
+// the span of some number of months, tiling the calendar from the given first month, which
+// contains a moment
+fn months_containing(
+    now: NaiveDateTime,
+    first_month: u32,
+    months: i64,
+) -> (NaiveDateTime, NaiveDateTime) {
+    let n = now.year() as i64 * 12 + now.month0() as i64 - (first_month as i64 - 1);
+    let n = n - n.rem_euclid(months) + first_month as i64 - 1;
+    let d1 = first_moment_of_day(n.div_euclid(12) as i32, n.rem_euclid(12) as u32 + 1, 1);
+    (d1, add_months(d1, months))
+}
+
+/// The units of time into which time expressions are resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
+pub enum Period {
+    /// A calendar year.
     Year,
+    /// A fiscal year, as defined by the configuration.
+    FiscalYear,
+    /// A calendar quarter: January through March, April through June, and so on.
+    Quarter,
+    /// A quarter of the fiscal year.
+    FiscalQuarter,
+    /// A calendar month.
     Month,
+    /// A week, beginning on Monday or Sunday depending on the configuration.
     Week,
+    /// A calendar day.
     Day,
+    /// An hour.
     Hour,
+    /// A minute.
     Minute,
+    /// A second.
     Second,
+    /// A pay period, as defined by the configuration.
     PayPeriod,
 }
 
+impl Period {
+    // orders periods from finest to coarsest
+    fn rank(&self) -> u8 {
+        match self {
+            Period::Second => 0,
+            Period::Minute => 1,
+            Period::Hour => 2,
+            Period::Day => 3,
+            Period::Week => 4,
+            Period::PayPeriod => 5,
+            Period::Month => 6,
+            Period::Quarter | Period::FiscalQuarter => 7,
+            Period::Year | Period::FiscalYear => 8,
+        }
+    }
+    fn finer(self, other: Period) -> Period {
+        if self.rank() <= other.rank() {
+            self
+        } else {
+            other
+        }
+    }
+}
+
+// determine the granularity of a parsed expression
+fn granularity(m: &Match, config: &Config) -> Period {
+    let period = base_granularity(m, config);
+    if !(m.has("two_times") || m.has("bound") || m.has("onward"))
+        && (m.has("clusivity") || m.has("sub_period"))
+    {
+        // a third of a month is some days, of a day some hours
+        return if period.rank() > Period::Day.rank() {
+            Period::Day
+        } else {
+            period.finer(Period::Hour)
+        };
+    }
+    period
+}
+
+// the granularity of an expression disregarding any part of a period it picks out
+fn base_granularity(m: &Match, config: &Config) -> Period {
+    if m.has("universal") {
+        return Period::Year;
+    }
+    if let Some(two_times) = m.name("two_times") {
+        if two_times.has("times_on_day") {
+            return Period::Second;
+        }
+        if let Some(interval) = two_times.name("iso_interval") {
+            let endpoints = interval.as_str().split('/').collect::<Vec<_>>();
+            return if endpoints.len() == 1 || endpoints.iter().any(|e| e.contains(['T', 't'])) {
+                Period::Second
+            } else if endpoints
+                .iter()
+                .any(|e| !e.starts_with(['P', 'p']) && e.replace('-', "").len() > 6)
+            {
+                Period::Day
+            } else {
+                Period::Month
+            };
+        }
+        let children = two_times.children().unwrap();
+        return granularity(&children[0], config).finer(granularity(&children[2], config));
+    }
+    if let Some(adjustment) = m.name("adjustment") {
+        // "two weeks from Friday" is another Friday, so it is as fine as Friday
+        let from = adjustment
+            .name("direction")
+            .is_some_and(|d| d.as_str().eq_ignore_ascii_case("from"))
+            && !m
+                .name("adverb")
+                .is_some_and(|a| a.as_str().eq_ignore_ascii_case("now"));
+        if !from {
+            return match adjustment.name("unit") {
+                Some(unit) => unit_period(unit.as_str()),
+                None => Period::Day,
+            };
+        }
+    }
+    if m.has("time") || m.has("precise_time") {
+        return Period::Second;
+    }
+    if m.has("nth_of") {
+        return Period::Day;
+    }
+    if m.has("anchor") {
+        // an anchor of whole days is as fine as a day
+        return match anchor(m, config) {
+            Ok((d1, d2)) if d1 < d2 && d1.time() == d2.time() && d1.hour() == 0 => Period::Day,
+            _ => Period::Second,
+        };
+    }
+    if m.has("day_part") {
+        return Period::Hour;
+    }
+    if let Some(window) = m.name("rolling_window") {
+        return if window.has("business_unit") {
+            Period::Day
+        } else if window.has("fortnight") {
+            Period::Week
+        } else {
+            unit_period(window.name("unit").unwrap().as_str())
+        };
+    }
+    if let Some(relative_period) = m.name("relative_period") {
+        return match relative_period.name("displacement") {
+            Some(displacement) => unit_period(displacement.as_str()),
+            None => Period::Day,
+        };
+    }
+    if let Some(named_quarter) = m.name("named_quarter") {
+        return if named_quarter.has("fiscal") || named_quarter.has("fy") {
+            Period::FiscalQuarter
+        } else {
+            Period::Quarter
+        };
+    }
+    if m.has("fiscal_year") {
+        return Period::FiscalYear;
+    }
+    if let Some(period) = m.name("iso_period") {
+        return if period.as_str().to_uppercase().contains('W') {
+            Period::Week
+        } else {
+            Period::Month
+        };
+    }
+    if m.has("month_and_year") {
+        return Period::Month;
+    }
+    if let Some(modified_period) = m.name("modified_period") {
+        if modified_period.has("a_month") {
+            return Period::Month;
+        }
+        if modified_period.has("a_day") || modified_period.has("business_day") {
+            return Period::Day;
+        }
+        return match ModifiablePeriod::from_match(
+            modified_period.name("modifiable_period").unwrap(),
+        ) {
+            ModifiablePeriod::Week => Period::Week,
+            ModifiablePeriod::Weekend => Period::Day,
+            ModifiablePeriod::Month => Period::Month,
+            ModifiablePeriod::Year => Period::Year,
+            ModifiablePeriod::PayPeriod => Period::PayPeriod,
+            ModifiablePeriod::Quarter => Period::Quarter,
+            ModifiablePeriod::FiscalQuarter => Period::FiscalQuarter,
+            ModifiablePeriod::FiscalYear => Period::FiscalYear,
+        };
+    }
+    if let Some(adverb) = m.name("adverb") {
+        return match adverb.as_str().to_lowercase().as_str() {
+            "now" => config.period,
+            "tonight" => Period::Hour,
+            _ => Period::Day,
+        };
+    }
+    if m.has("some_day") || m.has("a_day") {
+        return Period::Day;
+    }
+    if m.has("a_month") {
+        return Period::Month;
+    }
+    if m.has("year") {
+        return Period::Year;
+    }
+    config.period
+}
+
+// the period corresponding to a unit such as "weeks" or "minute"
+fn unit_period(unit: &str) -> Period {
+    match unit.chars().next().expect("empty string") {
+        'y' | 'Y' => Period::Year,
+        'w' | 'W' => Period::Week,
+        'd' | 'D' => Period::Day,
+        'h' | 'H' => Period::Hour,
+        'm' | 'M' => match unit.chars().nth(1).expect("too short") {
+            'o' | 'O' => Period::Month,
+            _ => Period::Minute,
+        },
+        _ => Period::Second,
+    }
+}
+
+// move a moment by some number of units such as weeks or months; months and years are
+// calendrical, so a month after January 31 is the last day of February
+fn shift(t: NaiveDateTime, count: i64, unit: &str) -> Result<NaiveDateTime, TimeError> {
+    let shifted = match unit_period(unit) {
+        Period::Year => count
+            .checked_mul(12)
+            .and_then(|months| checked_add_months(t, months)),
+        Period::Month => checked_add_months(t, count),
+        period => {
+            let length = match period {
+                Period::Week => 7 * 24 * 60 * 60,
+                Period::Day => 24 * 60 * 60,
+                Period::Hour => 60 * 60,
+                Period::Minute => 60,
+                _ => 1,
+            };
+            // a Duration holds no more than i64::MAX milliseconds
+            count
+                .checked_mul(length)
+                .filter(|s| s.abs() <= i64::MAX / 1000)
+                .and_then(|s| t.checked_add_signed(Duration::seconds(s)))
+        }
+    };
+    shifted.ok_or_else(|| {
+        TimeError::ImpossibleDate(format!(
+            "shifting {} by {} {} is out of range",
+            t, count, unit
+        ))
+    })
+}
+
+// add some number of months to a moment, clamping the day to the end of the month
+fn add_months(t: NaiveDateTime, months: i64) -> NaiveDateTime {
+    checked_add_months(t, months).unwrap()
+}
+
+// add some number of months to a moment, or None if the result is out of range
+fn checked_add_months(t: NaiveDateTime, months: i64) -> Option<NaiveDateTime> {
+    let months = (t.year() as i64 * 12 + t.month0() as i64).checked_add(months)?;
+    let year = months.div_euclid(12);
+    if year.abs() > i32::MAX as i64 {
+        return None;
+    }
+    let year = year as i32;
+    let month = months.rem_euclid(12) as u32 + 1;
+    // the first of the month must exist before its length can be found
+    NaiveDate::from_ymd_opt(year, month, 1)?;
+    let day = t.day().min(days_in_month(year, month));
+    Some(NaiveDate::from_ymd_opt(year, month, day)?.and_time(t.time()))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (y, m) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(y, m, 1)
+        .unwrap()
+        .pred_opt()
+        .unwrap()
+        .day()
+}
+
 fn weekday(s: &str) -> Weekday {
     match s.chars().nth(0).expect("empty string") {
         'm' | 'M' => Weekday::Mon,
@@ -1879,59 +4508,136 @@ fn weekday(s: &str) -> Weekday {
 }
 
 // adjust a period relative to another period -- e.g., "one week before June" or "five minutes around 12:00 PM"
-fn adjust(d1: NaiveDateTime, d2: NaiveDateTime, m: &Match) -> (NaiveDateTime, NaiveDateTime) {
+fn adjust(
+    d1: NaiveDateTime,
+    d2: NaiveDateTime,
+    m: &Match,
+    config: &Config,
+) -> Result<(NaiveDateTime, NaiveDateTime), TimeError> {
     if let Some(adjustment) = m.name("adjustment") {
-        let count = count(adjustment.name("count").unwrap()) as i64;
-        let unit = match adjustment
-            .name("unit")
-            .unwrap()
-            .as_str()
-            .chars()
-            .nth(0)
-            .unwrap()
-        {
-            'w' | 'W' => Duration::weeks(count),
-            'd' | 'D' => Duration::days(count),
-            'h' | 'H' => Duration::hours(count),
-            'm' | 'M' => Duration::minutes(count),
-            _ => Duration::seconds(count),
+        // "within 5 days of May 6" runs from the start of May 6 to 5 days after its end
+        let direction = match adjustment.name("direction") {
+            Some(direction) => direction.as_str(),
+            None => "within",
+        };
+        if let Some(unit) = adjustment.name("business_unit") {
+            // "the business day after" is simply one business day after
+            let count = adjustment.name("count").map_or(Ok(1), count)? as i64;
+            let weekdays_only = weekdays_only(unit.as_str());
+            let before = whole_day(nth_business_day(d1.date(), -count, weekdays_only, config));
+            let after = whole_day(nth_business_day(
+                last_day(d1, d2),
+                count,
+                weekdays_only,
+                config,
+            ));
+            return Ok(match direction.to_lowercase().as_str() {
+                "before" => before,
+                "after" | "from" => after,
+                "within" => (d1, after.1),
+                _ => (before.0, after.1),
+            });
+        }
+        let unit = adjustment.name("unit").unwrap().as_str();
+        let count = match adjustment.name("count") {
+            Some(n) => count(n)? as i64,
+            None => {
+                // "the week after" is the week following the period, not a moment a week later
+                match direction.to_lowercase().as_str() {
+                    "before" => return Ok((shift(d1, -1, unit)?, d1)),
+                    "after" => return Ok((d2, shift(d2, 1, unit)?)),
+                    _ => 1,
+                }
+            }
         };
-        let direction = adjustment.name("direction").unwrap().as_str();
-        match direction.chars().nth(0).unwrap() {
+        if direction == "within" {
+            return Ok((d1, shift(d2, count, unit)?));
+        }
+        Ok(match direction.chars().nth(0).unwrap() {
+            'f' | 'F' => {
+                if m.name("adverb")
+                    .is_some_and(|a| a.as_str().eq_ignore_ascii_case("now"))
+                {
+                    // "2 weeks from now" is the same as it is without adjustment
+                    return displaced_period(count, unit, config);
+                }
+                // "two weeks from Friday" is another Friday
+                let d = shift(d1, count, unit)?;
+                (d, d + (d2 - d1))
+            }
             'b' | 'B' => {
                 if direction.len() == 6 {
                     // before
-                    let d = d1 - unit;
+                    let d = shift(d1, -count, unit)?;
                     (d, d)
                 } else {
                     // before and after
-                    (d1 - unit, d1 + unit)
+                    (shift(d1, -count, unit)?, shift(d1, count, unit)?)
                 }
             }
             _ => match direction.chars().nth(1).unwrap() {
                 'f' | 'F' => {
-                    let d = d2 + unit;
+                    // months and years count from the start of the period, keeping its day of
+                    // the month where possible
+                    let d = match unit_period(unit) {
+                        Period::Year | Period::Month => shift(d1, count, unit)?,
+                        _ => shift(d2, count, unit)?,
+                    };
                     (d, d)
                 }
                 _ => {
-                    let d1 = d1 - Duration::milliseconds(unit.num_milliseconds() / 2);
-                    let d2 = d1 + unit;
+                    let span = shift(d1, count, unit)? - d1;
+                    let d1 = d1 - Duration::milliseconds(span.num_milliseconds() / 2);
+                    let d2 = d1 + span;
                     (d1, d2)
                 }
             },
+        })
+    } else {
+        Ok((d1, d2))
+    }
+}
+
+// whether a unit such as "weekdays" or "business days" counts only the days of the work week
+fn weekdays_only(unit: &str) -> bool {
+    unit.to_lowercase().starts_with("weekday")
+}
+
+// the nth business day after a date, or before it if n is negative
+fn nth_business_day(d: NaiveDate, n: i64, weekdays_only: bool, config: &Config) -> NaiveDate {
+    let step = Duration::days(n.signum());
+    let mut d = d;
+    for _ in 0..n.abs() {
+        d += step;
+        while !config.business_day(d, weekdays_only) {
+            d += step;
         }
+    }
+    d
+}
+
+// the day on which a period ends; a moment ends on the day it begins
+fn last_day(d1: NaiveDateTime, d2: NaiveDateTime) -> NaiveDate {
+    if d2 > d1 {
+        (d2 - Duration::nanoseconds(1)).date()
     } else {
-        (d1, d2)
+        d1.date()
     }
 }
 
+// the first and last moments of a day
+fn whole_day(d: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+    let d = d.and_hms_opt(0, 0, 0).unwrap();
+    (d, d + Duration::days(1))
+}
+
 // for converting a few cardinal numbers and integer expressions
-fn count(m: &Match) -> u32 {
+fn count(m: &Match) -> Result<u32, TimeError> {
     let s = m.as_str();
     if m.has("a_count") {
         // cardinal numbers
-        match s.chars().nth(0).expect("impossibly short") {
-            'o' | 'O' => 1,
+        Ok(match s.chars().nth(0).expect("impossibly short") {
+            'a' | 'A' | 'o' | 'O' => 1,
             't' | 'T' => match s.chars().nth(1).expect("impossibly short") {
                 'w' | 'W' => 2,
                 'h' | 'H' => 3,
@@ -1947,9 +4653,10 @@ fn count(m: &Match) -> u32 {
             },
             'e' | 'E' => 8,
             _ => 9,
-        }
+        })
     } else {
-        s.parse::<u32>().unwrap()
+        s.parse::<u32>()
+            .map_err(|_| TimeError::ImpossibleDate(format!("{} is too large a count", s)))
     }
 }
 
@@ -0,0 +1,137 @@
+/*!
+
+Filtering the lines of a log file by the time range of a time expression.
+
+A `LogFilter` pairs the range described by an expression such as "yesterday from 2pm to 4pm"
+with a description of the timestamps in the log. It streams through the log, keeping only
+those lines whose timestamps fall in the range. Lines without a timestamp, such as the
+continuation lines of a stack trace, are kept or dropped along with the line before them.
+
+# Example
+
+```rust
+# extern crate two_timer;
+# use two_timer::log_filter::{LogFilter, Timestamp};
+# use two_timer::Config;
+# use chrono::NaiveDate;
+let now = NaiveDate::from_ymd_opt(1969, 5, 6).unwrap().and_hms_opt(12, 0, 0).unwrap();
+let filter = LogFilter::new(
+    "yesterday from 2pm to 4pm",
+    Timestamp::Format("%Y-%m-%d %H:%M:%S".to_string()),
+    Some(Config::new().now(now)),
+)
+.unwrap();
+let log = "\
+1969-05-05 13:59:59 too early
+1969-05-05 14:30:00 just right
+1969-05-05 16:00:00 too late
+";
+let mut kept = vec![];
+filter.filter(log.as_bytes(), &mut kept).unwrap();
+assert_eq!("1969-05-05 14:30:00 just right\n", String::from_utf8(kept).unwrap());
+```
+
+*/
+
+use crate::{parse_range, Config, TimeError, TimeRange};
+use chrono::naive::{NaiveDate, NaiveDateTime};
+use regex::Regex;
+use std::io::{self, BufRead, Write};
+
+/// How to find the timestamp in a log line.
+#[derive(Debug, Clone)]
+pub enum Timestamp {
+    /// A [strftime format](https://docs.rs/chrono/latest/chrono/format/strftime/index.html)
+    /// for the timestamp at the beginning of each line, such as `"%Y-%m-%d %H:%M:%S"`.
+    /// If the format has no time, the timestamp is the first moment of the day.
+    Format(String),
+    /// A regular expression locating the timestamp anywhere in the line. The text of the
+    /// first capture group, or of the entire match if there is no group, is interpreted as a
+    /// time expression, so it may be anything `parse` understands.
+    Pattern(Regex),
+}
+
+/// Keeps the lines of a log whose timestamps fall in a time range.
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    range: TimeRange,
+    timestamp: Timestamp,
+    sorted: bool,
+    config: Config,
+}
+
+impl LogFilter {
+    /// Constructs a filter from a time expression and a description of the log's
+    /// timestamps. The configuration is used both to interpret the expression and, for
+    /// `Timestamp::Pattern`, to interpret the timestamps.
+    pub fn new(
+        expression: &str,
+        timestamp: Timestamp,
+        config: Option<Config>,
+    ) -> Result<LogFilter, TimeError> {
+        let config = config.unwrap_or_default();
+        let range = parse_range(expression, Some(config.clone()))?;
+        Ok(LogFilter {
+            range,
+            timestamp,
+            sorted: false,
+            config,
+        })
+    }
+    /// Returns a copy of the filter with whether the log is sorted by time set to the
+    /// parameter supplied. By default logs are not assumed to be sorted. A filter on a
+    /// sorted log stops reading at the first timestamp after the range.
+    pub fn sorted(&self, sorted: bool) -> LogFilter {
+        let mut f = self.clone();
+        f.sorted = sorted;
+        f
+    }
+    /// The time range lines must fall in.
+    pub fn range(&self) -> &TimeRange {
+        &self.range
+    }
+    /// The timestamp of a line, if it has one.
+    pub fn timestamp(&self, line: &str) -> Option<NaiveDateTime> {
+        match &self.timestamp {
+            Timestamp::Format(format) => NaiveDateTime::parse_and_remainder(line, format)
+                .map(|(t, _)| t)
+                .or_else(|_| {
+                    NaiveDate::parse_and_remainder(line, format)
+                        .map(|(d, _)| d.and_hms_opt(0, 0, 0).unwrap())
+                })
+                .ok(),
+            Timestamp::Pattern(rx) => {
+                let cap = rx.captures(line)?;
+                let text = cap.get(1).unwrap_or_else(|| cap.get(0).unwrap()).as_str();
+                parse_range(text, Some(self.config.clone()))
+                    .ok()
+                    .map(|r| r.start())
+            }
+        }
+    }
+    /// Whether a line's timestamp falls in the range. This is `None` if the line has no
+    /// timestamp.
+    pub fn contains(&self, line: &str) -> Option<bool> {
+        self.timestamp(line)
+            .map(|t| self.range.start() <= t && t < self.range.end())
+    }
+    /// Reads a log, writing out the lines in the range. Returns the number of lines written.
+    pub fn filter<R: BufRead, W: Write>(&self, input: R, mut output: W) -> io::Result<usize> {
+        let mut count = 0;
+        let mut keeping = false;
+        for line in input.lines() {
+            let line = line?;
+            if let Some(t) = self.timestamp(&line) {
+                if self.sorted && t >= self.range.end() {
+                    break;
+                }
+                keeping = self.range.start() <= t && t < self.range.end();
+            }
+            if keeping {
+                writeln!(output, "{}", line)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
@@ -0,0 +1,392 @@
+/*!
+
+Recurring times such as "every Monday at 9", "daily", or "the first Friday of each month".
+
+A `Recurrence` is a schedule rather than a time: it names no particular Monday, only the rule
+by which Mondays are chosen. Given some anchor moment it produces the successive ranges of
+the schedule beginning at or after the anchor. An occurrence with a time of day, like a time
+parsed by `parse`, lasts a second; one without lasts the day or, as with "monthly", the entire
+period.
+
+These are the schedules understood:
+
+* "hourly", "daily", "weekly", "monthly", "yearly", "annually"
+* "every day", "every other week", "every 3 months"
+* "every Monday", "every other Tuesday", "every Monday and Thursday", "every weekday"
+* "every May 6th", "each July the 4th"
+* "the first Friday of each month", "the last Monday of every month", "monthly on the second Tuesday"
+* "the 15th of every month", "the last day of each month", "every month on the 1st"
+
+Any of these which picks out particular days may be followed by a time: "every Monday at 9",
+"daily at half past three", "the 15th of every month at noon".
+
+# Example
+
+```rust
+# extern crate two_timer;
+# use two_timer::recurrence::Recurrence;
+# use two_timer::Config;
+# use chrono::NaiveDate;
+let recurrence = Recurrence::new("every Monday at 9", None).unwrap();
+let anchor = NaiveDate::from_ymd_opt(1969, 5, 6).unwrap().and_hms_opt(12, 3, 5).unwrap();
+let starts = recurrence
+    .iter(anchor)
+    .take(2)
+    .map(|(start, _)| start.to_string())
+    .collect::<Vec<_>>();
+assert_eq!(vec!["1969-05-12 09:00:00", "1969-05-19 09:00:00"], starts);
+```
+
+*/
+
+use crate::{
+    a_month, add_months, count, days_in_month, first_moment_of_day, last_moment, moment_to_period,
+    n_day, o_day, time, unit_period, weekday, Config, Period, TimeError, GRAMMAR,
+};
+use chrono::naive::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Datelike, Duration, Weekday};
+use pidgin::{Grammar, Matcher};
+use std::collections::VecDeque;
+
+lazy_static! {
+    // schedules such as "every other Tuesday at 3 PM", sharing the vocabulary of GRAMMAR
+    #[doc(hidden)]
+    pub static ref RECURRENCE_GRAMMAR: Grammar = grammar!{
+        (?ibBw)
+
+        TOP -> r(r"\A") <recurrence> r(r"\z")
+
+        recurrence -> <schedule> <at_time>?
+
+        schedule => <frequency> | <every> | <day_of_month>
+
+        every -> <each> <interval>? <unit>      // every other week
+        every -> <each> <interval>? <weekdays>  // every Monday and Thursday
+        every -> <each> <workday>               // every weekday
+        every -> <each> <date>                  // every May 6th
+
+        day_of_month -> ("the")? <nth> <a_day>? ("day")? <of_each> ("month")  // the first Friday of each month
+        day_of_month -> <monthly> ("on")? ("the")? <nth> <a_day>? ("day")?    // every month on the 15th
+
+        // a repeated rule only captures its last repetition, so the days are enumerated
+        weekdays -> <a_day> <and_day>? <and_day>? <and_day>? <and_day>? <and_day>? <and_day>?
+
+        and_day -> (",")? ("and")? <a_day>
+
+        date -> <a_month> ("the")? g(GRAMMAR.rule("o_n_day").unwrap())
+
+        at_time -> ("at")? g(GRAMMAR.rule("clock_time").unwrap())
+
+        interval => ("other") | g(GRAMMAR.rule("count").unwrap())
+
+        nth => g(GRAMMAR.rule("o_day").unwrap()) | <last>
+
+        a_day     => g(GRAMMAR.rule("a_day").unwrap())
+        a_month   => g(GRAMMAR.rule("a_month").unwrap())
+        each      => [["every", "each"]]
+        frequency => [["hourly", "daily", "weekly", "monthly", "yearly", "annually"]]
+        last      => ("last")
+        monthly   => [["monthly", "every month", "each month"]]
+        of_each   => [["of each", "of every", "in each", "in every"]]
+        unit      => g(GRAMMAR.rule("unit").unwrap())
+        workday   => [["weekday", "workday"]]
+    };
+}
+lazy_static! {
+    #[doc(hidden)]
+    pub static ref RECURRENCE_MATCHER: Matcher = RECURRENCE_GRAMMAR.matcher().unwrap();
+}
+
+/// A schedule of recurring time ranges.
+#[derive(Debug, Clone)]
+pub struct Recurrence {
+    pub(crate) frequency: Period,
+    pub(crate) interval: u32,
+    pub(crate) weekdays: Vec<Weekday>,
+    pub(crate) position: Option<i32>,
+    pub(crate) month_day: Option<i32>,
+    pub(crate) month: Option<u32>,
+    pub(crate) time: Option<NaiveTime>,
+    pub(crate) config: Config,
+}
+
+impl Recurrence {
+    /// Constructs a recurrence from an expression such as "every Monday at 9". The
+    /// configuration determines the first day of the week, which matters for schedules such
+    /// as "every other Sunday", and the days of the work week, which are those of "every
+    /// weekday".
+    pub fn new(expression: &str, config: Option<Config>) -> Result<Recurrence, TimeError> {
+        let parse = match RECURRENCE_MATCHER.parse(expression) {
+            Some(parse) => parse,
+            None => {
+                return Err(TimeError::Parse(format!(
+                    "could not parse \"{}\" as a recurrence",
+                    expression
+                )))
+            }
+        };
+        let mut recurrence = Recurrence {
+            frequency: Period::Day,
+            interval: 1,
+            weekdays: vec![],
+            position: None,
+            month_day: None,
+            month: None,
+            time: None,
+            config: config.unwrap_or_default(),
+        };
+        if let Some(frequency) = parse.name("frequency") {
+            recurrence.frequency = match frequency.as_str().chars().next().unwrap() {
+                'h' | 'H' => Period::Hour,
+                'd' | 'D' => Period::Day,
+                'w' | 'W' => Period::Week,
+                'm' | 'M' => Period::Month,
+                _ => Period::Year,
+            };
+        } else if let Some(every) = parse.name("every") {
+            if let Some(interval) = every.name("interval") {
+                recurrence.interval = match interval.name("count") {
+                    Some(n) => count(n)?,
+                    None => 2, // every other
+                };
+            }
+            if let Some(unit) = every.name("unit") {
+                recurrence.frequency = unit_period(unit.as_str());
+            } else if let Some(days) = every.name("weekdays") {
+                recurrence.frequency = Period::Week;
+                recurrence.weekdays = days
+                    .all_names("a_day")
+                    .iter()
+                    .map(|d| weekday(d.as_str()))
+                    .collect();
+            } else if every.has("workday") {
+                recurrence.frequency = Period::Week;
+                recurrence.weekdays = recurrence.config.work_week.clone();
+            } else {
+                let date = every.name("date").unwrap();
+                let month = a_month(date);
+                let day = if date.has("n_day") {
+                    n_day(date)
+                } else {
+                    o_day(date, month)
+                };
+                // February 29th recurs, if only every four years
+                if NaiveDate::from_ymd_opt(2000, month, day).is_none() {
+                    return Err(TimeError::ImpossibleDate(format!(
+                        "{} never occurs",
+                        date.as_str()
+                    )));
+                }
+                recurrence.frequency = Period::Year;
+                recurrence.month = Some(month);
+                recurrence.month_day = Some(day as i32);
+            }
+        } else {
+            let day_of_month = parse.name("day_of_month").unwrap();
+            let nth = day_of_month.name("nth").unwrap();
+            let n = if nth.has("last") {
+                -1
+            } else if let Some(roman) = nth.name("roman") {
+                if !roman.as_str().to_lowercase().starts_with('k') {
+                    return Err(TimeError::Parse(format!(
+                        "the {} fall on different days in different months",
+                        roman.as_str()
+                    )));
+                }
+                1
+            } else {
+                o_day(nth, 1) as i32
+            };
+            recurrence.frequency = Period::Month;
+            if let Some(d) = day_of_month.name("a_day") {
+                if n > 5 {
+                    return Err(TimeError::ImpossibleDate(format!(
+                        "no month has a {} {}",
+                        nth.as_str(),
+                        d.as_str()
+                    )));
+                }
+                recurrence.weekdays = vec![weekday(d.as_str())];
+                recurrence.position = Some(n);
+            } else {
+                recurrence.month_day = Some(n);
+            }
+        }
+        recurrence
+            .weekdays
+            .sort_by_key(|d| d.num_days_from_monday());
+        recurrence.weekdays.dedup();
+        if let Some(clock) = parse.name("clock_time") {
+            if recurrence.whole_periods() {
+                return Err(TimeError::Parse(format!(
+                    "\"{}\" gives a time without saying on which days",
+                    expression
+                )));
+            }
+            let (hour, minute, second, _) = time(clock);
+            recurrence.time = NaiveTime::from_hms_opt(hour, minute, second);
+        }
+        Ok(recurrence)
+    }
+    /// The period by which the schedule repeats: `Period::Week` for "every Monday",
+    /// `Period::Month` for "the 15th of every month".
+    pub fn frequency(&self) -> Period {
+        self.frequency
+    }
+    /// How many periods separate the periods containing occurrences: 2 for "every other
+    /// week", 3 for "every 3 days".
+    pub fn interval(&self) -> u32 {
+        self.interval
+    }
+    /// The days of the week on which the recurrence falls, from Monday to Sunday. This is
+    /// empty if the days of the week don't matter.
+    pub fn weekdays(&self) -> &[Weekday] {
+        &self.weekdays
+    }
+    /// Which of the weekdays of the month the recurrence falls on: 1 for "the first Friday
+    /// of each month", -1 for "the last Friday".
+    pub fn position(&self) -> Option<i32> {
+        self.position
+    }
+    /// The day of the month on which the recurrence falls; -1 is the last day.
+    pub fn month_day(&self) -> Option<i32> {
+        self.month_day
+    }
+    /// The month of a yearly recurrence such as "every May 6th".
+    pub fn month(&self) -> Option<u32> {
+        self.month
+    }
+    /// The time of day of each occurrence, if any.
+    pub fn time(&self) -> Option<NaiveTime> {
+        self.time
+    }
+    /// The successive occurrences of the recurrence beginning at or after the anchor, each
+    /// as the first and last moments of a range. The intervals of schedules such as "every
+    /// other Tuesday" are counted from the first occurrence.
+    pub fn iter(&self, anchor: NaiveDateTime) -> Occurrences<'_> {
+        let (period, _) = moment_to_period(anchor, &self.frequency, &self.config);
+        Occurrences {
+            recurrence: self,
+            anchor,
+            period: Some(period),
+            started: false,
+            pending: VecDeque::new(),
+        }
+    }
+    // whether each occurrence is an entire period, as with "monthly"
+    pub(crate) fn whole_periods(&self) -> bool {
+        match self.frequency {
+            Period::Day => false,
+            Period::Week => self.weekdays.is_empty(),
+            Period::Month | Period::Year => self.month_day.is_none() && self.position.is_none(),
+            _ => true,
+        }
+    }
+    // the occurrences in the period beginning at the given moment
+    fn occurrences(&self, period: NaiveDateTime) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        if self.whole_periods() {
+            return vec![moment_to_period(period, &self.frequency, &self.config)];
+        }
+        let days = match self.frequency {
+            Period::Day => vec![period.date()],
+            Period::Week => (0..7)
+                .map(|i| period.date() + Duration::days(i))
+                .filter(|d| self.weekdays.contains(&d.weekday()))
+                .collect(),
+            _ => {
+                let year = period.year();
+                let month = self.month.unwrap_or_else(|| period.month());
+                let last = days_in_month(year, month) as i32;
+                if let Some(day) = self.month_day {
+                    let day = if day < 0 { last + 1 + day } else { day };
+                    (day <= last)
+                        .then(|| NaiveDate::from_ymd_opt(year, month, day as u32).unwrap())
+                        .into_iter()
+                        .collect()
+                } else {
+                    let days = (1..=last)
+                        .map(|d| NaiveDate::from_ymd_opt(year, month, d as u32).unwrap())
+                        .filter(|d| d.weekday() == self.weekdays[0])
+                        .collect::<Vec<_>>();
+                    let n = self.position.unwrap();
+                    let i = if n < 0 { days.len() as i32 + n } else { n - 1 };
+                    days.get(i as usize).cloned().into_iter().collect()
+                }
+            }
+        };
+        days.into_iter()
+            .map(|d| match self.time {
+                Some(t) => moment_to_period(d.and_time(t), &Period::Second, &self.config),
+                None => moment_to_period(
+                    first_moment_of_day(d.year(), d.month(), d.day()),
+                    &Period::Day,
+                    &self.config,
+                ),
+            })
+            .collect()
+    }
+    // the beginning of the period some number of periods after the given one, if this is
+    // before the last year of the calendar
+    fn advance(&self, period: NaiveDateTime, n: u32) -> Option<NaiveDateTime> {
+        let n = n as i64;
+        let t = match self.frequency {
+            Period::Year | Period::Month => {
+                let months = if self.frequency == Period::Year {
+                    n * 12
+                } else {
+                    n
+                };
+                if period.year() as i64 + months / 12 + 1 >= last_moment().year() as i64 {
+                    return None;
+                }
+                add_months(period, months)
+            }
+            Period::Week => period.checked_add_signed(Duration::weeks(n))?,
+            Period::Day => period.checked_add_signed(Duration::days(n))?,
+            Period::Hour => period.checked_add_signed(Duration::hours(n))?,
+            Period::Minute => period.checked_add_signed(Duration::minutes(n))?,
+            _ => period.checked_add_signed(Duration::seconds(n))?,
+        };
+        if t.year() < last_moment().year() {
+            Some(t)
+        } else {
+            None
+        }
+    }
+}
+
+/// The occurrences of a `Recurrence` from some anchor, as returned by `Recurrence::iter`.
+/// These continue until the end of time.
+#[derive(Debug)]
+pub struct Occurrences<'a> {
+    recurrence: &'a Recurrence,
+    anchor: NaiveDateTime,
+    period: Option<NaiveDateTime>,
+    started: bool,
+    pending: VecDeque<(NaiveDateTime, NaiveDateTime)>,
+}
+
+impl<'a> Iterator for Occurrences<'a> {
+    type Item = (NaiveDateTime, NaiveDateTime);
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            let period = self.period?;
+            let anchor = self.anchor;
+            self.pending.extend(
+                self.recurrence
+                    .occurrences(period)
+                    .into_iter()
+                    .filter(|(start, _)| *start >= anchor),
+            );
+            // the interval is counted from the period of the first occurrence
+            let step = if self.started || !self.pending.is_empty() {
+                self.started = true;
+                self.recurrence.interval
+            } else {
+                1
+            };
+            self.period = self.recurrence.advance(period, step);
+        }
+        self.pending.pop_front()
+    }
+}
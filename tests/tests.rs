@@ -1,8 +1,19 @@
 extern crate two_timer;
-use two_timer::{parsable, parse, Config, TimeError};
+use two_timer::formatter::describe;
+use two_timer::holidays::{Holiday, Holidays};
+use two_timer::log_filter::{LogFilter, Timestamp};
+use two_timer::recurrence::Recurrence;
+use two_timer::{
+    find_all, parsable, parse, parse_all, parse_duration, parse_range, parse_zoned, Config,
+    DateOrder, DayPart, TimeError, Zone,
+};
 extern crate chrono;
 use chrono::naive::NaiveDate;
-use chrono::{Duration, Local, NaiveDateTime};
+use chrono::{Duration, FixedOffset, Local, NaiveDateTime};
+extern crate regex;
+use regex::Regex;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
 
 // a debugging method to print out the parse tree
 // fn show_me(p: &str) {
@@ -92,7 +103,7 @@ fn day_5_6_69_at_3_30_pm() {
 }
 
 #[test]fn numeric_months() {
-    for m in (1..=12).into_iter() {
+    for m in 1..=12 {
         let date: String = format!("1969-{}-1", &m);
         let then = precise_moment(1969, m, 1, 0, 0, 0);
         let (start, end, _) = parse(&date, None).unwrap();
@@ -1007,7 +1018,7 @@ fn very_specific_up_to_very_specific() {
 #[test]
 fn somewhat_specific_through_somewhat_specific() {
     let d1 = precise_moment(2014, 10, 6, 8, 57, 00);
-    let d2 = precise_moment(2020, 3, 6, 17, 28, 01);
+    let d2 = precise_moment(2020, 3, 6, 17, 28, 1);
     let (start, end, _) = parse("2014-10-06 08:57 - 2020-03-06 17:28", None).unwrap();
     assert_eq!(d1, start);
     assert_eq!(d2, end);
@@ -1240,7 +1251,7 @@ fn regression_12pm() {
         assert_eq!(d1, start);
         assert_eq!(d2, end);
     } else {
-        assert!(false);
+        panic!();
     }
 }
 
@@ -1252,7 +1263,7 @@ fn year_2000() {
         assert_eq!(d1, start);
         assert_eq!(d2, end);
     } else {
-        assert!(false);
+        panic!();
     }
 }
 
@@ -1313,7 +1324,7 @@ fn ordinals() {
                 }
                 Err(e) => {
                     println!("{:?}", e);
-                    assert!(false, "didn't match");
+                    panic!("didn't match");
                 }
             }
         }
@@ -1338,10 +1349,7 @@ fn kalends_nones_ids() {
     ];
     for (i, m) in months.iter().enumerate() {
         let i = (i + 1) as u32;
-        let big_month = match i {
-            3 | 5 | 7 | 10 => true,
-            _ => false,
-        };
+        let big_month = matches!(i, 3 | 5 | 7 | 10);
         // kalends
         let d1 = precise_moment(2018, i, 1, 0, 0, 0);
         let d2 = d1 + Duration::days(1);
@@ -1353,7 +1361,7 @@ fn kalends_nones_ids() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
         // nones
@@ -1367,7 +1375,7 @@ fn kalends_nones_ids() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
         // ides
@@ -1381,7 +1389,7 @@ fn kalends_nones_ids() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1408,7 +1416,7 @@ fn day_and_month() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1434,7 +1442,7 @@ fn day_and_month_default_to_future() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1452,7 +1460,7 @@ fn one_week_before_may_6_1969() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1470,7 +1478,7 @@ fn one_week_after_may_6_1969() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1493,7 +1501,7 @@ fn one_week_before_and_after_may_6_1969() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1512,7 +1520,7 @@ fn one_week_around_may_6_1969() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1534,7 +1542,7 @@ fn number_before_test() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1551,7 +1559,7 @@ fn noon() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
     let now = first_moment_of_day(1969, 5, 6);
@@ -1562,7 +1570,7 @@ fn noon() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
 }
@@ -1579,7 +1587,7 @@ fn simple_noon_past_and_future() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
     let d1 = d1 + Duration::days(1);
@@ -1591,7 +1599,7 @@ fn simple_noon_past_and_future() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
 }
@@ -1607,7 +1615,7 @@ fn midnight() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
 }
@@ -1655,7 +1663,7 @@ fn displacement() {
                 }
                 Err(e) => {
                     println!("{:?}", e);
-                    assert!(false, "didn't match");
+                    panic!("didn't match");
                 }
             }
             let d1 = now + displacement1;
@@ -1668,7 +1676,7 @@ fn displacement() {
                 }
                 Err(e) => {
                     println!("{:?}", e);
-                    assert!(false, "didn't match");
+                    panic!("didn't match");
                 }
             }
         }
@@ -1685,7 +1693,7 @@ fn friday_the_13th() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
 }
@@ -1700,7 +1708,7 @@ fn the_31st() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
 }
@@ -1716,7 +1724,7 @@ fn specific_time() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
 }
@@ -1732,7 +1740,7 @@ fn no_space_before_pm() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
     match parse("1969-05-06 at 1:00PM", None) {
@@ -1742,7 +1750,7 @@ fn no_space_before_pm() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
     match parse("1969-05-06 at 1:00:00PM", None) {
@@ -1752,15 +1760,15 @@ fn no_space_before_pm() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
 }
 
 #[test]
 fn relative_time_regression() {
+    // "24" shouldn't cause a panic
     parse("24", None).unwrap();
-    assert!(true, "'24' didn't cause a panic");
 }
 
 #[test]
@@ -1775,7 +1783,7 @@ fn since_yesterday() {
         }
         Err(e) => {
             println!("{:?}", e);
-            assert!(false, "didn't match");
+            panic!("didn't match");
         }
     }
 }
@@ -1801,7 +1809,7 @@ fn since_noon() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1815,9 +1823,6 @@ fn since_may() {
         "since may",
         "since the start of may",
         "since the beginning of may",
-        "after may",
-        "after the start of may",
-        "after the beginning of may",
     ] {
         match parse(expr, Some(Config::new().now(now))) {
             Ok((start, end, two_times)) => {
@@ -1827,7 +1832,7 @@ fn since_may() {
             }
             Err(e) => {
                 println!("{:?}", e);
-                assert!(false, "didn't match");
+                panic!("didn't match");
             }
         }
     }
@@ -1837,17 +1842,1695 @@ fn since_may() {
 fn since_the_end_of_may_misordered() {
     let then = first_moment_of_day(1969, 5, 1);
     let now = then + Duration::hours(5);
-    for expr in &["since the end of may", "after the end of may"] {
+    for expr in &["since the end of may"] {
         match parse(expr, Some(Config::new().now(now))) {
-            Ok((..)) => assert!(false, "this should not succeed"),
+            Ok((..)) => panic!("this should not succeed"),
             Err(e) => match e {
-                TimeError::Misordered(_) => assert!(true, "correct error"),
-                _ => assert!(false, "unexpected error: {:?}", e),
+                TimeError::Misordered(_) => (),
+                _ => panic!("unexpected error: {:?}", e),
             },
         }
     }
 }
 
+#[test]
+fn parse_range_agrees_with_parse() {
+    let now = precise_moment(1969, 5, 6, 12, 3, 5);
+    for phrase in [
+        "now",
+        "May 1969",
+        "3 PM",
+        "last week",
+        "from 5/6/69 to 3 PM on 5/7/69",
+        "since yesterday",
+        "always",
+    ]
+    .iter()
+    {
+        let config = Config::new().now(now);
+        let (start, end, is_range) = parse(phrase, Some(config.clone())).unwrap();
+        let range = parse_range(phrase, Some(config)).unwrap();
+        assert_eq!(start, range.start());
+        assert_eq!(end, range.end());
+        assert_eq!(is_range, range.is_range());
+    }
+}
+
+#[test]
+fn time_range_period() {
+    use two_timer::Period;
+    let now = precise_moment(1969, 5, 6, 12, 3, 5);
+    for (phrase, period) in [
+        ("now", Period::Minute),
+        ("today", Period::Day),
+        ("3 PM", Period::Second),
+        ("5/6/69", Period::Day),
+        ("Friday", Period::Day),
+        ("this week", Period::Week),
+        ("this weekend", Period::Day),
+        ("May 1969", Period::Month),
+        ("June", Period::Month),
+        ("1969", Period::Year),
+        ("2 hours ago", Period::Hour),
+        ("from May 1969 to 3 PM on 5/7/69", Period::Second),
+        ("always", Period::Year),
+    ]
+    .iter()
+    {
+        let range = parse_range(phrase, Some(Config::new().now(now))).unwrap();
+        assert_eq!(*period, range.period(), "{}", phrase);
+    }
+}
+
+#[test]
+fn time_range_text() {
+    let range = parse_range(" from May 1969 to June ", None).unwrap();
+    assert_eq!("from May 1969 to June", range.text());
+    assert!(range.is_range());
+}
+
+#[test]
+fn explicit_zones() {
+    let utc = Zone::Fixed(FixedOffset::east_opt(0).unwrap());
+    let east = Zone::Fixed(FixedOffset::east_opt(2 * 3600).unwrap());
+    let now = precise_moment(1969, 5, 6, 18, 0, 0);
+    for (phrase, zone, then) in [
+        ("3 PM UTC", east, precise_moment(1969, 5, 6, 17, 0, 0)),
+        ("3pm utc", east, precise_moment(1969, 5, 6, 17, 0, 0)),
+        ("15:00 +02:00", utc, precise_moment(1969, 5, 6, 13, 0, 0)),
+        ("15:00 UTC+2", utc, precise_moment(1969, 5, 6, 13, 0, 0)),
+        ("noon EST", utc, precise_moment(1969, 5, 6, 17, 0, 0)),
+        ("1969-05-06 12:03:05Z", east, precise_moment(1969, 5, 6, 14, 3, 5)),
+        ("1969-05-06 12:03:05-05:00", utc, precise_moment(1969, 5, 6, 17, 3, 5)),
+        ("3 PM", east, precise_moment(1969, 5, 6, 15, 0, 0)),
+    ]
+    .iter()
+    {
+        let config = Config::new().zone(*zone).now(now);
+        let (start, end, _) = parse(phrase, Some(config)).unwrap();
+        assert_eq!(*then, start, "{}", phrase);
+        assert_eq!(*then + Duration::seconds(1), end, "{}", phrase);
+    }
+}
+
+#[test]
+fn dashes_between_times_are_not_zones() {
+    let now = precise_moment(1969, 5, 6, 18, 0, 0);
+    let (start, end, is_range) = parse("9:00-10:00", Some(Config::new().now(now))).unwrap();
+    assert!(is_range);
+    assert_eq!(precise_moment(1969, 5, 6, 9, 0, 0), start);
+    assert_eq!(precise_moment(1969, 5, 6, 10, 0, 1), end);
+}
+
+#[test]
+fn conflicting_zones() {
+    match parse("3 PM UTC to 5 PM EST", None) {
+        Err(TimeError::Parse(_)) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn zoned_results() {
+    let zone = Zone::Fixed(FixedOffset::west_opt(5 * 3600).unwrap());
+    let config = Config::new().zone(zone);
+    let (start, end, _) = parse_zoned("May 6, 1969", Some(config)).unwrap();
+    assert_eq!("1969-05-06 00:00:00 -05:00", start.to_string());
+    assert_eq!("1969-05-07 00:00:00 -05:00", end.to_string());
+    assert_eq!(zone, start.timezone());
+}
+
+#[test]
+fn zone_keeps_now() {
+    let east = Zone::Fixed(FixedOffset::east_opt(2 * 3600).unwrap());
+    let utc = Zone::Fixed(FixedOffset::east_opt(0).unwrap());
+    let config = Config::new()
+        .zone(east)
+        .now(precise_moment(1969, 5, 6, 12, 0, 0))
+        .zone(utc);
+    let (start, _, _) = parse("now", Some(config)).unwrap();
+    assert_eq!(precise_moment(1969, 5, 6, 10, 0, 0), start);
+}
+
+#[cfg(feature = "tz")]
+#[test]
+fn dst_day_boundaries() {
+    let zone = Zone::Named(chrono_tz::America::New_York);
+    let config = Config::new()
+        .zone(zone)
+        .now(precise_moment(2023, 3, 12, 12, 0, 0));
+    let (start, end, _) = parse_zoned("today", Some(config.clone())).unwrap();
+    assert_eq!("2023-03-12 00:00:00 -05:00", start.to_string());
+    assert_eq!("2023-03-13 00:00:00 -04:00", end.to_string());
+    assert_eq!(Duration::hours(23), end - start);
+    // the timestamps keep their zone, so arithmetic follows its transitions
+    assert_eq!(zone, start.timezone());
+    assert_eq!(
+        "2023-03-13 01:00:00 -04:00",
+        (start + Duration::days(1)).to_string()
+    );
+    // 2:30 AM does not exist on this day
+    let (start, _, _) = parse_zoned("2:30 AM", Some(config)).unwrap();
+    assert_eq!("2023-03-12 03:30:00 -04:00", start.to_string());
+    // 1:30 AM happens twice on this day; the first is chosen
+    let config = Config::new()
+        .zone(zone)
+        .now(precise_moment(2023, 11, 5, 12, 0, 0));
+    let (start, _, _) = parse_zoned("1:30 AM", Some(config)).unwrap();
+    assert_eq!("2023-11-05 01:30:00 -04:00", start.to_string());
+}
+
+#[test]
+fn find_all_in_text() {
+    let now = precise_moment(1969, 5, 6, 12, 3, 5);
+    let text = "We worked from May 1969 to June 1970; the café opened at 3 PM yesterday.";
+    let found = find_all(text, Some(Config::new().now(now)));
+    assert_eq!(2, found.len());
+    assert_eq!("from May 1969 to June 1970", found[0].text());
+    assert!(found[0].is_range());
+    assert_eq!(first_moment_of_day(1969, 5, 1), found[0].start());
+    assert_eq!("at 3 PM yesterday", found[1].text());
+    assert_eq!("at 3 PM yesterday", &text[found[1].span()]);
+    assert_eq!(precise_moment(1969, 5, 5, 15, 0, 0), found[1].start());
+}
+
+#[test]
+fn find_all_ignores_implausible_words() {
+    let found = find_all("we may have 2 of them ever so often", None);
+    assert!(found.is_empty(), "{:?}", found);
+    let found = find_all("I saw it in May", None);
+    assert_eq!(1, found.len());
+}
+
+#[test]
+fn months_and_years_ago() {
+    let now = first_moment_of_day(1969, 5, 6);
+    let config = Config::new().now(now);
+    let (d1, d2, _) = parse("6 months ago", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1968, 11, 1), d1);
+    assert_eq!(first_moment_of_day(1968, 12, 1), d2);
+    let (d1, d2, _) = parse("2 years from now", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1971, 1, 1), d1);
+    assert_eq!(first_moment_of_day(1972, 1, 1), d2);
+    let config = config.now(first_moment_of_day(1969, 3, 31));
+    let (d1, d2, _) = parse("one month ago", Some(config)).unwrap();
+    assert_eq!(first_moment_of_day(1969, 2, 1), d1);
+    assert_eq!(first_moment_of_day(1969, 3, 1), d2);
+}
+
+#[test]
+fn months_and_years_adjustments() {
+    let d = first_moment_of_day(1968, 5, 6);
+    let (d1, d2, _) = parse("one year before May 6, 1969", None).unwrap();
+    assert_eq!(d, d1);
+    assert_eq!(d, d2);
+    // the day of the month is clamped to the length of the month
+    let d = first_moment_of_day(2020, 2, 29);
+    let (d1, d2, _) = parse("1 month before March 31, 2020", None).unwrap();
+    assert_eq!(d, d1);
+    assert_eq!(d, d2);
+    let d = first_moment_of_day(2019, 2, 28);
+    let (d1, d2, _) = parse("1 year before February 29, 2020", None).unwrap();
+    assert_eq!(d, d1);
+    assert_eq!(d, d2);
+    let (d1, d2, _) = parse("one month before and after March 31, 2021", None).unwrap();
+    assert_eq!(first_moment_of_day(2021, 2, 28), d1);
+    assert_eq!(first_moment_of_day(2021, 4, 30), d2);
+    for (phrase, d) in [
+        ("1 month after January 31, 2024", first_moment_of_day(2024, 2, 29)),
+        ("1 month after January 31, 2023", first_moment_of_day(2023, 2, 28)),
+        ("one year after February 29, 2020", first_moment_of_day(2021, 2, 28)),
+        ("2 months after May 6, 1969", first_moment_of_day(1969, 7, 6)),
+    ] {
+        let (d1, d2, _) = parse(phrase, None).unwrap();
+        assert_eq!(d, d1, "{}", phrase);
+        assert_eq!(d, d2, "{}", phrase);
+    }
+}
+
+#[test]
+fn huge_displacements() {
+    let config = Config::new().now(first_moment_of_day(1969, 5, 6));
+    for phrase in [
+        "300000 years ago",
+        "300000 years from now",
+        "the last 300000 years",
+        "the next 30000000 months",
+        "300000 years before May 6, 1969",
+        "300000 years after May 6, 1969",
+        "4000000000 weeks ago",
+        "the last 4000000000 fortnights",
+    ] {
+        match parse(phrase, Some(config.clone())) {
+            Err(TimeError::ImpossibleDate(_)) => (),
+            result => panic!("{}: {:?}", phrase, result),
+        }
+    }
+}
+
+#[test]
+fn quarters() {
+    let config = Config::new().now(first_moment_of_day(1969, 5, 6));
+    for (expr, start, end) in [
+        ("Q3", (1969, 7, 1), (1969, 10, 1)),
+        ("Q3 2023", (2023, 7, 1), (2023, 10, 1)),
+        ("q4 '68", (1968, 10, 1), (1969, 1, 1)),
+        ("this quarter", (1969, 4, 1), (1969, 7, 1)),
+        ("last quarter", (1969, 1, 1), (1969, 4, 1)),
+        ("next quarter", (1969, 7, 1), (1969, 10, 1)),
+    ] {
+        let (d1, d2, _) = parse(expr, Some(config.clone())).unwrap();
+        assert_eq!(first_moment_of_day(start.0, start.1, start.2), d1, "{}", expr);
+        assert_eq!(first_moment_of_day(end.0, end.1, end.2), d2, "{}", expr);
+    }
+}
+
+#[test]
+fn fiscal_years() {
+    let config = Config::new()
+        .now(first_moment_of_day(1969, 5, 6))
+        .fiscal_year_start(10);
+    for (expr, start, end) in [
+        ("FY2024", (2023, 10, 1), (2024, 10, 1)),
+        ("FY 2024", (2023, 10, 1), (2024, 10, 1)),
+        ("fiscal year 2024", (2023, 10, 1), (2024, 10, 1)),
+        ("this fiscal year", (1968, 10, 1), (1969, 10, 1)),
+        ("last fiscal year", (1967, 10, 1), (1968, 10, 1)),
+        ("fiscal Q1", (1968, 10, 1), (1969, 1, 1)),
+        ("Q3 FY2024", (2024, 4, 1), (2024, 7, 1)),
+        ("fiscal Q1 2024", (2023, 10, 1), (2024, 1, 1)),
+        ("next fiscal quarter", (1969, 7, 1), (1969, 10, 1)),
+        // calendar quarters are unaffected
+        ("Q1 2024", (2024, 1, 1), (2024, 4, 1)),
+    ] {
+        let (d1, d2, _) = parse(expr, Some(config.clone())).unwrap();
+        assert_eq!(first_moment_of_day(start.0, start.1, start.2), d1, "{}", expr);
+        assert_eq!(first_moment_of_day(end.0, end.1, end.2), d2, "{}", expr);
+    }
+    // by default the fiscal year is the calendar year
+    let (d1, d2, _) = parse("FY2024", None).unwrap();
+    assert_eq!(first_moment_of_day(2024, 1, 1), d1);
+    assert_eq!(first_moment_of_day(2025, 1, 1), d2);
+}
+
+#[test]
+fn parse_all_numeric_dates() {
+    let interpretations = parse_all("5/6/69", None).unwrap();
+    let found = interpretations
+        .iter()
+        .map(|(r, order)| (r.start(), order.unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (first_moment_of_day(1969, 5, 6), DateOrder::MonthDayYear),
+            (first_moment_of_day(1969, 6, 5), DateOrder::DayMonthYear),
+        ],
+        found
+    );
+    // the same date read two ways is only one interpretation
+    let interpretations = parse_all("5/5/69", None).unwrap();
+    assert_eq!(1, interpretations.len());
+    assert_eq!(Some(DateOrder::MonthDayYear), interpretations[0].1);
+    // only one order makes sense
+    let interpretations = parse_all("31/12/2020 at 3 PM", None).unwrap();
+    assert_eq!(1, interpretations.len());
+    assert_eq!(Some(DateOrder::DayMonthYear), interpretations[0].1);
+    assert_eq!(
+        precise_moment(2020, 12, 31, 15, 0, 0),
+        interpretations[0].0.start()
+    );
+}
+
+#[test]
+fn parse_all_unambiguous() {
+    let interpretations = parse_all("May 6, 1969", None).unwrap();
+    assert_eq!(1, interpretations.len());
+    assert_eq!(None, interpretations[0].1);
+    match parse_all("2023-02-30", None) {
+        Err(TimeError::ImpossibleDate(_)) => (),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn preferred_date_order() {
+    let config = Config::new()
+        .now(first_moment_of_day(1969, 5, 6))
+        .date_order(DateOrder::DayMonthYear);
+    for (expr, start) in [
+        ("05/06/2023", first_moment_of_day(2023, 6, 5)),
+        ("5/6/69", first_moment_of_day(1969, 6, 5)),
+        ("5/6", first_moment_of_day(1969, 6, 5)),
+        ("05/06/2023 12:03:05", precise_moment(2023, 6, 5, 12, 3, 5)),
+        ("2023-05-06", first_moment_of_day(2023, 5, 6)),
+        // other orders are still tried
+        ("12/13/2023", first_moment_of_day(2023, 12, 13)),
+        ("12/13", first_moment_of_day(1969, 12, 13)),
+    ] {
+        let (d1, _, _) = parse(expr, Some(config.clone())).unwrap();
+        assert_eq!(start, d1, "{}", expr);
+    }
+}
+
+#[test]
+fn exclusive_date_order() {
+    let config = Config::new()
+        .now(first_moment_of_day(1969, 5, 6))
+        .date_orders(&[DateOrder::DayMonthYear]);
+    let (d1, _, _) = parse("13/5/69", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1969, 5, 13), d1);
+    for expr in ["12/13/2023", "12/13", "2023-05-06"] {
+        match parse(expr, Some(config.clone())) {
+            Err(TimeError::Parse(_)) => (),
+            other => panic!("unexpected result for {}: {:?}", expr, other),
+        }
+    }
+    let interpretations = parse_all("5/6/69", Some(config)).unwrap();
+    assert_eq!(1, interpretations.len());
+    assert_eq!(first_moment_of_day(1969, 6, 5), interpretations[0].0.start());
+}
+
+#[test]
+fn times_on_a_day() {
+    let config = Config::new().now(first_moment_of_day(1969, 5, 6));
+    let (d1, d2, is_range) = parse("yesterday from 2pm to 4pm", Some(config.clone())).unwrap();
+    assert_eq!(precise_moment(1969, 5, 5, 14, 0, 0), d1);
+    assert_eq!(precise_moment(1969, 5, 5, 16, 0, 0), d2);
+    assert!(is_range);
+    let (d1, d2, _) = parse("May 5, 1969 2 PM through 4 PM", Some(config.clone())).unwrap();
+    assert_eq!(precise_moment(1969, 5, 5, 14, 0, 0), d1);
+    assert_eq!(precise_moment(1969, 5, 5, 16, 0, 1), d2);
+    match parse("yesterday from 4pm to 2pm", Some(config)) {
+        Err(TimeError::Misordered(_)) => (),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn log_filter() {
+    let log = "\
+1969-05-05 13:59:59 too early
+1969-05-05 14:30:00 just right
+  continuation
+1969-05-05 16:00:00 too late
+  continuation
+1969-05-05 14:45:00 out of order
+";
+    let config = Config::new().now(first_moment_of_day(1969, 5, 6));
+    let filter = LogFilter::new(
+        "yesterday from 2pm to 4pm",
+        Timestamp::Format("%Y-%m-%d %H:%M:%S".to_string()),
+        Some(config.clone()),
+    )
+    .unwrap();
+    let mut kept = vec![];
+    assert_eq!(3, filter.filter(log.as_bytes(), &mut kept).unwrap());
+    assert_eq!(
+        "1969-05-05 14:30:00 just right\n  continuation\n1969-05-05 14:45:00 out of order\n",
+        String::from_utf8(kept).unwrap()
+    );
+    // a sorted log is only read up to the end of the range
+    let mut kept = vec![];
+    assert_eq!(2, filter.sorted(true).filter(log.as_bytes(), &mut kept).unwrap());
+    // timestamps may be found anywhere and are interpreted as time expressions
+    let filter = LogFilter::new(
+        "yesterday",
+        Timestamp::Pattern(Regex::new(r"at (.*?)\]").unwrap()),
+        Some(config),
+    )
+    .unwrap();
+    assert_eq!(Some(true), filter.contains("[logged at 3 PM on 5/5/69] hello"));
+    assert_eq!(Some(false), filter.contains("[logged at May 6, 1969] hello"));
+    assert_eq!(None, filter.contains("hello"));
+}
+
+#[test]
+fn describe_ranges() {
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (expr, description) in [
+        ("last week", "last week"),
+        ("5/6/1968", "May 6, 1968"),
+        ("tomorrow from 3 PM to 5 PM", "3 PM to 5 PM tomorrow"),
+        ("1969-05-06 10:00:00 to 1969-05-06 12:00:00", "10 AM to noon today"),
+        ("2 hours ago", "2 hours ago"),
+        ("last month", "last month"),
+        ("June 1970", "June 1970"),
+        ("this weekend", "this weekend"),
+        ("always", "always"),
+        ("the past 2 hours", "the past 2 hours"),
+        ("the next 3 days", "the next 3 days"),
+        (
+            "1969-05-06 12:03:05 to 1969-05-08 12:04:17",
+            "12:03:05 today to 12:04:17 on May 8, 1969",
+        ),
+        // a week neither adjacent to nor the current one is named by its days
+        ("April 14 through April 20", "April 14, 1969 through April 20, 1969"),
+        ("May 19 through May 25", "May 19, 1969 through May 25, 1969"),
+    ] {
+        let (start, end, _) = parse(expr, Some(config.clone())).unwrap();
+        assert_eq!(description, describe(start, end, Some(config.clone())), "{}", expr);
+    }
+    // the timestamps in the fallback description name the moments themselves
+    let start = precise_moment(1969, 5, 6, 0, 30, 0) + Duration::milliseconds(500);
+    let end = precise_moment(1969, 5, 7, 0, 0, 0);
+    assert_eq!(
+        "1969-05-06 00:30:00.500 to 1969-05-07 00:00:00",
+        describe(start, end, Some(config))
+    );
+}
+
+#[test]
+fn describe_round_trips() {
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for expr in [
+        "now",
+        "today",
+        "yesterday",
+        "Friday",
+        "next Tuesday",
+        "this year",
+        "1066",
+        "March 15, 44 BC",
+        "May 6, 69 AD",
+        "Q3 2023",
+        "next quarter",
+        "3:15:27 PM on June 1, 2000",
+        "May 6 through May 8",
+        "May 1968 through June 1970",
+        "last week through next week",
+        "1969-05-06 12:03:05 to 1972-01-01 10:00:01",
+        "the beginning of time to now",
+        "May 6, 1969 to the end of time",
+        "before May 1969",
+        "2020 onward",
+        "15 minutes around 12:13:43 PM",
+        "5 minutes before and after midnight",
+        "10 seconds from now",
+    ] {
+        let (start, end, _) = parse(expr, Some(config.clone())).unwrap();
+        let description = describe(start, end, Some(config.clone()));
+        let (s, e, _) = parse(&description, Some(config.clone())).unwrap();
+        assert_eq!((start, end), (s, e), "{} => {}", expr, description);
+    }
+}
+
+#[test]
+fn day_parts() {
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (phrase, start, end) in [
+        ("this morning", (6, 6), (6, 12)),
+        ("tomorrow afternoon", (7, 12), (7, 17)),
+        ("yesterday evening", (5, 17), (5, 21)),
+        ("Friday night", (2, 21), (3, 6)),
+        ("this Friday night", (9, 21), (10, 6)),
+        ("tonight", (6, 21), (7, 6)),
+        ("last night", (5, 21), (6, 6)),
+        ("tomorrow in the afternoon", (7, 12), (7, 17)),
+    ] {
+        let (d1, d2, is_range) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(precise_moment(1969, 5, start.0, start.1, 0, 0), d1, "{}", phrase);
+        assert_eq!(precise_moment(1969, 5, end.0, end.1, 0, 0), d2, "{}", phrase);
+        assert!(!is_range, "{}", phrase);
+    }
+}
+
+#[test]
+fn configured_day_parts() {
+    let config = Config::new()
+        .now(precise_moment(1969, 5, 6, 12, 3, 5))
+        .day_part(DayPart::Morning, 5, 11)
+        .day_part(DayPart::Night, 22, 24);
+    let (d1, d2, _) = parse("this morning", Some(config.clone())).unwrap();
+    assert_eq!(precise_moment(1969, 5, 6, 5, 0, 0), d1);
+    assert_eq!(precise_moment(1969, 5, 6, 11, 0, 0), d2);
+    let (d1, d2, _) = parse("tonight", Some(config)).unwrap();
+    assert_eq!(precise_moment(1969, 5, 6, 22, 0, 0), d1);
+    assert_eq!(first_moment_of_day(1969, 5, 7), d2);
+}
+
+#[test]
+fn modified_weekday_with_time() {
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    let (d1, d2, _) = parse("next Tuesday at 3 PM", Some(config)).unwrap();
+    assert_eq!(precise_moment(1969, 5, 13, 15, 0, 0), d1);
+    assert_eq!(precise_moment(1969, 5, 13, 15, 0, 1), d2);
+}
+
+#[test]
+fn colloquial_times() {
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (phrase, hour, minute) in [
+        ("half past three", 3, 30),
+        ("quarter to five", 4, 45),
+        ("a quarter past 3 PM", 15, 15),
+        ("ten o'clock", 10, 0),
+        ("10 o'clock PM", 22, 0),
+        ("three PM", 15, 0),
+        ("ten past nine", 9, 10),
+        ("twenty-five minutes to six", 5, 35),
+        ("5 minutes past 3", 3, 5),
+        ("17 minutes to 4 pm", 15, 43),
+    ] {
+        let (d1, d2, _) =
+            parse(&format!("{} on May 6, 1969", phrase), Some(config.clone())).unwrap();
+        assert_eq!(
+            precise_moment(1969, 5, 6, hour, minute, 0),
+            d1,
+            "{}",
+            phrase
+        );
+        assert_eq!(d1 + Duration::seconds(1), d2, "{}", phrase);
+    }
+    let (d1, d2, is_range) =
+        parse("tomorrow from three PM to half past four PM", Some(config.clone())).unwrap();
+    assert_eq!(precise_moment(1969, 5, 7, 15, 0, 0), d1);
+    assert_eq!(precise_moment(1969, 5, 7, 16, 30, 0), d2);
+    assert!(is_range);
+    assert!(parse("0 minutes to 4", Some(config)).is_err());
+}
+
+#[test]
+fn iso_8601() {
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (phrase, start, end) in [
+        (
+            "2023-05-06T12:03:05.123Z",
+            "2023-05-06 12:03:05.123",
+            "2023-05-06 12:03:05.124",
+        ),
+        (
+            "2023-05-06T12:03:05+02:00",
+            "2023-05-06 10:03:05",
+            "2023-05-06 10:03:06",
+        ),
+        (
+            "20230506T120305,5-0500",
+            "2023-05-06 17:03:05.500",
+            "2023-05-06 17:03:05.501",
+        ),
+        (
+            "2023-05-06 00:30:00",
+            "2023-05-06 00:30:00",
+            "2023-05-06 00:30:01",
+        ),
+        (
+            "2023-05-06T24:00",
+            "2023-05-07 00:00:00",
+            "2023-05-07 00:00:01",
+        ),
+        ("2023-W18-6", "2023-05-06 00:00:00", "2023-05-07 00:00:00"),
+        (
+            "2023W186T10:00",
+            "2023-05-06 10:00:00",
+            "2023-05-06 10:00:01",
+        ),
+        ("2023-W18", "2023-05-01 00:00:00", "2023-05-08 00:00:00"),
+        ("2023-126", "2023-05-06 00:00:00", "2023-05-07 00:00:00"),
+        (
+            "2023-126T10:00:00",
+            "2023-05-06 10:00:00",
+            "2023-05-06 10:00:01",
+        ),
+        ("2023-05", "2023-05-01 00:00:00", "2023-06-01 00:00:00"),
+    ] {
+        let (d1, d2, _) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(start, d1.to_string(), "{}", phrase);
+        assert_eq!(end, d2.to_string(), "{}", phrase);
+    }
+    for phrase in ["2023-366", "2021-W53-1", "2023-02-30T10:00"] {
+        match parse(phrase, Some(config.clone())) {
+            Err(TimeError::ImpossibleDate(_)) => (),
+            other => panic!("unexpected result for {}: {:?}", phrase, other),
+        }
+    }
+}
+
+#[test]
+fn iso_intervals() {
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (phrase, start, end) in [
+        (
+            "2023-01-01/2023-02-01",
+            "2023-01-01 00:00:00",
+            "2023-02-01 00:00:00",
+        ),
+        (
+            "2023-05-06T10:00Z/PT1H30M",
+            "2023-05-06 10:00:00",
+            "2023-05-06 11:30:00",
+        ),
+        (
+            "PT2H30M/2023-05-06",
+            "2023-05-05 21:30:00",
+            "2023-05-06 00:00:00",
+        ),
+        (
+            "2023-01-31/P1M",
+            "2023-01-31 00:00:00",
+            "2023-02-28 00:00:00",
+        ),
+        ("P3D", "1969-05-03 12:03:05", "1969-05-06 12:03:05"),
+        ("PT1.5H", "1969-05-06 10:33:05", "1969-05-06 12:03:05"),
+    ] {
+        let (d1, d2, is_range) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(start, d1.to_string(), "{}", phrase);
+        assert_eq!(end, d2.to_string(), "{}", phrase);
+        assert!(is_range, "{}", phrase);
+    }
+    let (d1, d2, _) = parse("P1Y2M", Some(config.clone().default_to_past(false))).unwrap();
+    assert_eq!(precise_moment(1969, 5, 6, 12, 3, 5), d1);
+    assert_eq!(precise_moment(1970, 7, 6, 12, 3, 5), d2);
+    match parse("2023-02-01/2023-01-01", Some(config.clone())) {
+        Err(TimeError::Misordered(_)) => (),
+        other => panic!("unexpected result {:?}", other),
+    }
+    for phrase in ["P", "P1.5M", "P1D/P2D"] {
+        match parse(phrase, Some(config.clone())) {
+            Err(TimeError::Parse(_)) => (),
+            other => panic!("unexpected result for {}: {:?}", phrase, other),
+        }
+    }
+    for phrase in ["P99999999999999999999D", "PT99999999999H", "P9999999999Y"] {
+        match parse(phrase, Some(config.clone())) {
+            Err(TimeError::ImpossibleDate(_)) => (),
+            other => panic!("unexpected result for {}: {:?}", phrase, other),
+        }
+    }
+}
+
+#[test]
+fn durations() {
+    for (phrase, duration) in [
+        ("3 hours 20 minutes", Duration::minutes(200)),
+        ("1.5 days", Duration::hours(36)),
+        ("a fortnight", Duration::weeks(2)),
+        ("90 min", Duration::minutes(90)),
+        ("2h30m", Duration::minutes(150)),
+        ("half an hour", Duration::minutes(30)),
+        ("1 hour, 30 minutes and 10 seconds", Duration::seconds(5410)),
+        ("two weeks", Duration::weeks(2)),
+        ("PT3H20M", Duration::minutes(200)),
+    ] {
+        assert_eq!(
+            Some(duration),
+            parse_duration(phrase).unwrap().fixed(),
+            "{}",
+            phrase
+        );
+    }
+    let duration = parse_duration("1.5 years 2 days").unwrap();
+    assert_eq!(18, duration.months());
+    assert_eq!(Duration::days(2), duration.duration());
+    assert_eq!(None, duration.fixed());
+    assert_eq!(
+        precise_moment(1971, 11, 8, 12, 0, 0),
+        duration.after(precise_moment(1970, 5, 6, 12, 0, 0)).unwrap()
+    );
+    assert!(parse_duration("1.5 months").is_err());
+    assert!(parse_duration("three").is_err());
+    // huge quantities are errors rather than panics
+    for phrase in [
+        "99999999999 seconds",
+        "4294967295 weeks 4294967295 weeks 4294967295 weeks 4294967295 weeks",
+        "99999999999999999999.5 days",
+        "4294967295 years",
+    ] {
+        match parse_duration(phrase) {
+            Err(TimeError::ImpossibleDate(_)) => (),
+            other => panic!("unexpected result for {}: {:?}", phrase, other),
+        }
+    }
+    assert!(parse("99999999999 seconds ago", None).is_err());
+    assert!(Recurrence::new("every 99999999999 days", None).is_err());
+}
+
+#[test]
+fn recurrences() {
+    let anchor = precise_moment(1969, 5, 6, 12, 3, 5);
+    for (phrase, starts) in [
+        (
+            "every Monday at 9",
+            vec![(1969, 5, 12, 9), (1969, 5, 19, 9), (1969, 5, 26, 9)],
+        ),
+        (
+            "daily",
+            vec![(1969, 5, 7, 0), (1969, 5, 8, 0), (1969, 5, 9, 0)],
+        ),
+        (
+            "the first Friday of each month",
+            vec![(1969, 6, 6, 0), (1969, 7, 4, 0), (1969, 8, 1, 0)],
+        ),
+        (
+            "every other Tuesday at 3 PM",
+            vec![(1969, 5, 6, 15), (1969, 5, 20, 15), (1969, 6, 3, 15)],
+        ),
+        (
+            "every Monday and Friday",
+            vec![(1969, 5, 9, 0), (1969, 5, 12, 0), (1969, 5, 16, 0)],
+        ),
+        (
+            "every weekday at noon",
+            vec![(1969, 5, 7, 12), (1969, 5, 8, 12), (1969, 5, 9, 12)],
+        ),
+        (
+            "the last day of every month",
+            vec![(1969, 5, 31, 0), (1969, 6, 30, 0), (1969, 7, 31, 0)],
+        ),
+        (
+            "every month on the 31st",
+            vec![(1969, 5, 31, 0), (1969, 7, 31, 0), (1969, 8, 31, 0)],
+        ),
+        (
+            "monthly on the last Monday",
+            vec![(1969, 5, 26, 0), (1969, 6, 30, 0), (1969, 7, 28, 0)],
+        ),
+        (
+            "every Feb 29th",
+            vec![(1972, 2, 29, 0), (1976, 2, 29, 0), (1980, 2, 29, 0)],
+        ),
+        (
+            "every 3 hours",
+            vec![(1969, 5, 6, 13), (1969, 5, 6, 16), (1969, 5, 6, 19)],
+        ),
+    ] {
+        let recurrence = Recurrence::new(phrase, None).unwrap();
+        let found = recurrence
+            .iter(anchor)
+            .take(3)
+            .map(|(s, _)| s)
+            .collect::<Vec<_>>();
+        let expected = starts
+            .into_iter()
+            .map(|(y, m, d, h)| precise_moment(y, m, d, h, 0, 0))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, found, "{}", phrase);
+    }
+}
+
+#[test]
+fn recurrence_ranges() {
+    let anchor = precise_moment(1969, 5, 6, 12, 3, 5);
+    let recurrence = Recurrence::new("every other month", None).unwrap();
+    assert_eq!(two_timer::Period::Month, recurrence.frequency());
+    assert_eq!(2, recurrence.interval());
+    let ranges = recurrence.iter(anchor).take(2).collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (
+                first_moment_of_day(1969, 6, 1),
+                first_moment_of_day(1969, 7, 1)
+            ),
+            (
+                first_moment_of_day(1969, 8, 1),
+                first_moment_of_day(1969, 9, 1)
+            ),
+        ],
+        ranges
+    );
+    let recurrence = Recurrence::new("every Monday at 9", None).unwrap();
+    let (start, end) = recurrence.iter(anchor).next().unwrap();
+    assert_eq!(start + Duration::seconds(1), end);
+    let recurrence = Recurrence::new("every Sunday, Friday and Monday", None).unwrap();
+    assert_eq!(
+        &[
+            chrono::Weekday::Mon,
+            chrono::Weekday::Fri,
+            chrono::Weekday::Sun
+        ],
+        recurrence.weekdays()
+    );
+}
+
+#[test]
+fn bad_recurrences() {
+    for phrase in [
+        "weekly at 9",
+        "the 6th Friday of every month",
+        "every February 30th",
+        "the ides of every month",
+        "every now and then",
+    ] {
+        assert!(Recurrence::new(phrase, None).is_err(), "{}", phrase);
+    }
+}
+
+#[cfg(feature = "ical")]
+#[test]
+fn rrule_round_trips() {
+    use two_timer::ical::{parse_rrule, rrule};
+    let anchor = precise_moment(1969, 5, 6, 12, 3, 5);
+    for (phrase, rule) in [
+        (
+            "every Monday at 9",
+            "FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0;BYSECOND=0",
+        ),
+        ("daily", "FREQ=DAILY"),
+        ("every other month", "FREQ=MONTHLY;INTERVAL=2"),
+        ("the first Friday of each month", "FREQ=MONTHLY;BYDAY=1FR"),
+        ("monthly on the last Monday", "FREQ=MONTHLY;BYDAY=-1MO"),
+        ("the last day of every month", "FREQ=MONTHLY;BYMONTHDAY=-1"),
+        ("every Feb 29th", "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29"),
+        (
+            "every weekday at noon",
+            "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=12;BYMINUTE=0;BYSECOND=0",
+        ),
+        ("every 3 hours", "FREQ=HOURLY;INTERVAL=3"),
+    ] {
+        let recurrence = Recurrence::new(phrase, None).unwrap();
+        assert_eq!(rule, rrule(&recurrence), "{}", phrase);
+        let parsed = parse_rrule(rule, None).unwrap();
+        assert_eq!(rule, rrule(&parsed), "{}", phrase);
+        assert_eq!(
+            recurrence.iter(anchor).take(3).collect::<Vec<_>>(),
+            parsed.iter(anchor).take(3).collect::<Vec<_>>(),
+            "{}",
+            phrase
+        );
+    }
+    let config = Config::new().monday_starts_week(false);
+    let recurrence = Recurrence::new("every other Sunday", Some(config)).unwrap();
+    assert_eq!(
+        "FREQ=WEEKLY;INTERVAL=2;BYDAY=SU;WKST=SU",
+        rrule(&recurrence)
+    );
+}
+
+#[cfg(feature = "ical")]
+#[test]
+fn rrules_from_calendars() {
+    use two_timer::ical::{parse_rrule, rrule};
+    for (rule, expected) in [
+        ("RRULE:FREQ=WEEKLY;BYDAY=TH,MO", "FREQ=WEEKLY;BYDAY=MO,TH"),
+        ("freq=daily;byday=mo,we", "FREQ=WEEKLY;BYDAY=MO,WE"),
+        (
+            "FREQ=MONTHLY;BYDAY=+2TU;BYHOUR=15",
+            "FREQ=MONTHLY;BYDAY=2TU;BYHOUR=15;BYMINUTE=0;BYSECOND=0",
+        ),
+        ("FREQ=WEEKLY;INTERVAL=1;WKST=MO", "FREQ=WEEKLY"),
+    ] {
+        assert_eq!(
+            expected,
+            rrule(&parse_rrule(rule, None).unwrap()),
+            "{}",
+            rule
+        );
+    }
+    for rule in [
+        "BYDAY=MO",
+        "FREQ=FORTNIGHTLY",
+        "FREQ=WEEKLY;COUNT=10",
+        "FREQ=MONTHLY;BYDAY=1MO,3MO",
+        "FREQ=MONTHLY;BYDAY=MO",
+        "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30",
+        "FREQ=MONTHLY;BYMONTHDAY=0",
+        "FREQ=MONTHLY;BYHOUR=9",
+        "FREQ=WEEKLY;WKST=WE",
+    ] {
+        assert!(parse_rrule(rule, None).is_err(), "{}", rule);
+    }
+}
+
+#[cfg(feature = "ical")]
+#[test]
+fn vevents() {
+    use two_timer::ical::{parse_vevent, recurring_vevent, vevent};
+    let config = Config::new()
+        .zone(Zone::Fixed(FixedOffset::west_opt(5 * 3600).unwrap()))
+        .now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for phrase in ["May 6, 1969", "last week", "3 PM to 5 PM tomorrow", "noon"] {
+        let (start, end, _) = parse(phrase, Some(config.clone())).unwrap();
+        let event = vevent(start, end).unwrap();
+        assert_eq!(
+            (start, end),
+            parse_vevent(&event, Some(config.clone())).unwrap(),
+            "{}",
+            phrase
+        );
+    }
+    // the UID and DTSTAMP lines, the latter being the time of writing, and the rest
+    let parts = |event: String| {
+        let lines = event
+            .split("\r\n")
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>();
+        (
+            lines[1].clone(),
+            lines[2].clone(),
+            [&lines[..1], &lines[3..]].concat().join("\r\n"),
+        )
+    };
+    let (start, end, _) = parse("3 PM to 5 PM tomorrow", Some(config.clone())).unwrap();
+    let (uid, stamp, rest) = parts(vevent(start, end).unwrap());
+    assert_eq!(
+        "BEGIN:VEVENT\r\nDTSTART:19690507T150000\r\nDTEND:19690507T170000\r\nEND:VEVENT\r\n",
+        rest
+    );
+    assert!(
+        uid.starts_with("UID:") && uid.ends_with("@two-timer"),
+        "{}",
+        uid
+    );
+    assert_eq!(uid, parts(vevent(start, end).unwrap()).0);
+    assert_ne!(
+        uid,
+        parts(vevent(start, end + Duration::hours(1)).unwrap()).0
+    );
+    let stamp = stamp.strip_prefix("DTSTAMP:").unwrap();
+    assert!(
+        NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%SZ").is_ok(),
+        "{}",
+        stamp
+    );
+    assert!(vevent(two_timer::first_moment(), two_timer::last_moment()).is_err());
+    let calendar = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:1@example.com\r\n\
+        DTSTART:19690506T200000Z\r\nDURATION:PT1H30M\r\nSUMMARY:lunch,\r\n  with folded line\r\n\
+        END:VEVENT\r\nEND:VCALENDAR\r\n";
+    assert_eq!(
+        (
+            precise_moment(1969, 5, 6, 15, 0, 0),
+            precise_moment(1969, 5, 6, 16, 30, 0)
+        ),
+        parse_vevent(calendar, Some(config.clone())).unwrap()
+    );
+    assert_eq!(
+        (
+            first_moment_of_day(1969, 5, 6),
+            first_moment_of_day(1969, 5, 7)
+        ),
+        parse_vevent("DTSTART;VALUE=DATE:19690506", None).unwrap()
+    );
+    assert!(parse_vevent("DTSTART:19690506T120000", None).is_err());
+    assert!(parse_vevent("DTSTART:19690506T120000\nDTEND:19690506T110000", None).is_err());
+    let recurrence = Recurrence::new("every other Tuesday at 3 PM", None).unwrap();
+    let event = recurring_vevent(&recurrence, precise_moment(1969, 5, 6, 12, 3, 5)).unwrap();
+    let (uid, stamp, rest) = parts(event);
+    assert_eq!(
+        "BEGIN:VEVENT\r\nDTSTART:19690506T150000\r\nDTEND:19690506T150001\r\n\
+        RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU;BYHOUR=15;BYMINUTE=0;BYSECOND=0\r\nEND:VEVENT\r\n",
+        rest
+    );
+    assert!(uid.starts_with("UID:"), "{}", uid);
+    assert!(stamp.starts_with("DTSTAMP:"), "{}", stamp);
+}
+
+#[test]
+fn holidays() {
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (phrase, start, end) in [
+        ("Christmas", (1969, 12, 25), (1969, 12, 26)),
+        ("Thanksgiving 2022", (2022, 11, 24), (2022, 11, 25)),
+        ("Easter Sunday", (1969, 4, 6), (1969, 4, 7)),
+        ("easter 2024", (2024, 3, 31), (2024, 4, 1)),
+        ("Good Friday, 2000", (2000, 4, 21), (2000, 4, 22)),
+        ("New Year's Eve", (1969, 12, 31), (1970, 1, 1)),
+        ("new years eve", (1969, 12, 31), (1970, 1, 1)),
+        ("Memorial Day 2023", (2023, 5, 29), (2023, 5, 30)),
+        ("the week after Labor Day", (1969, 9, 2), (1969, 9, 9)),
+        ("the day before Christmas 1968", (1968, 12, 24), (1968, 12, 25)),
+        ("from Christmas Eve through New Year's Day", (1969, 12, 24), (1970, 1, 2)),
+    ] {
+        let (d1, d2, _) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(first_moment_of_day(start.0, start.1, start.2), d1, "{}", phrase);
+        assert_eq!(first_moment_of_day(end.0, end.1, end.2), d2, "{}", phrase);
+    }
+    let (d1, _, _) = parse("Christmas 1968 at noon", Some(config.clone())).unwrap();
+    assert_eq!(precise_moment(1968, 12, 25, 12, 0, 0), d1);
+    let range = parse_range("  Thanksgiving 2022 ", Some(config.clone())).unwrap();
+    assert_eq!("Thanksgiving 2022", range.text());
+    assert_eq!(2..19, range.span());
+    assert!(parsable("Labor Day"));
+    assert!(parse("Christmasy", Some(config.clone())).is_err());
+    let config = config.holiday_calendar(Holidays::new());
+    assert!(parse("Christmas", Some(config)).is_err());
+}
+
+#[test]
+fn custom_holidays() {
+    let calendar = Holidays::western()
+        .add("Founders' Day", Holiday::Fixed(5, 6))
+        .add("Thanksgiving", Holiday::NthWeekday(10, chrono::Weekday::Mon, 2))
+        .add("Leap Day", Holiday::Fixed(2, 29));
+    let config = Config::new()
+        .now(precise_moment(1969, 5, 6, 12, 3, 5))
+        .holiday_calendar(calendar);
+    let (d1, _, _) = parse("founders day", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1969, 5, 6), d1);
+    let (d1, _, _) = parse("Thanksgiving 2022", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(2022, 10, 10), d1);
+    let (d1, _, _) = parse("Leap Day 1972", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1972, 2, 29), d1);
+    match parse("Leap Day 1969", Some(config.clone())) {
+        Err(TimeError::ImpossibleDate(s)) => assert!(s.starts_with("Leap Day")),
+        _ => panic!("Leap Day 1969 should be impossible"),
+    }
+    let found = find_all(
+        "The office closes on Founders' Day and reopens the week after Christmas.",
+        Some(config),
+    );
+    let texts = found.iter().map(|r| r.text()).collect::<Vec<_>>();
+    assert_eq!(vec!["Founders' Day", "the week after Christmas"], texts);
+    assert_eq!(21..34, found[0].span());
+}
+
+#[test]
+fn holiday_rules() {
+    for (year, month, day) in [(1969, 4, 6), (2000, 4, 23), (2024, 3, 31), (2038, 4, 25)] {
+        assert_eq!(
+            NaiveDate::from_ymd_opt(year, month, day),
+            two_timer::holidays::easter(year),
+            "{}",
+            year
+        );
+    }
+    let last_friday = Holiday::NthWeekday(5, chrono::Weekday::Fri, -1);
+    assert_eq!(NaiveDate::from_ymd_opt(1969, 5, 30), last_friday.date(1969));
+    let fifth_monday = Holiday::NthWeekday(5, chrono::Weekday::Mon, 5);
+    assert_eq!(None, fifth_monday.date(1969));
+    assert_eq!(NaiveDate::from_ymd_opt(1972, 5, 29), fifth_monday.date(1972));
+}
+
+#[test]
+fn anchors() {
+    let config = Config::new()
+        .now(precise_moment(1969, 5, 6, 12, 3, 5))
+        .anchor(
+            "the release freeze",
+            first_moment_of_day(1969, 6, 2),
+            first_moment_of_day(1969, 6, 9),
+        )
+        .anchor(
+            "the offsite",
+            precise_moment(1969, 4, 28, 9, 0, 0),
+            precise_moment(1969, 4, 28, 17, 0, 0),
+        )
+        .anchor(
+            "sprint 42",
+            first_moment_of_day(1969, 5, 5),
+            first_moment_of_day(1969, 5, 19),
+        )
+        .anchor(
+            "the Christmas party",
+            precise_moment(1968, 12, 20, 18, 0, 0),
+            precise_moment(1968, 12, 20, 18, 0, 1),
+        );
+    for (phrase, start, end) in [
+        (
+            "the release freeze",
+            first_moment_of_day(1969, 6, 2),
+            first_moment_of_day(1969, 6, 9),
+        ),
+        (
+            "2 days before the release freeze",
+            first_moment_of_day(1969, 5, 31),
+            first_moment_of_day(1969, 5, 31),
+        ),
+        (
+            "since the offsite",
+            precise_moment(1969, 4, 28, 17, 0, 0),
+            precise_moment(1969, 5, 6, 12, 3, 5),
+        ),
+        (
+            "from Sprint  42 through the release freeze",
+            first_moment_of_day(1969, 5, 5),
+            first_moment_of_day(1969, 6, 9),
+        ),
+        (
+            "the week after the Christmas party",
+            precise_moment(1968, 12, 20, 18, 0, 1),
+            precise_moment(1968, 12, 27, 18, 0, 1),
+        ),
+    ] {
+        let (d1, d2, _) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(start, d1, "{}", phrase);
+        assert_eq!(end, d2, "{}", phrase);
+    }
+    let range = parse_range("sprint 42", Some(config.clone())).unwrap();
+    assert_eq!(two_timer::Period::Day, range.period());
+    assert_eq!("sprint 42", range.text());
+    let range = parse_range("the offsite", Some(config.clone())).unwrap();
+    assert_eq!(two_timer::Period::Second, range.period());
+    let found = find_all(
+        "We shipped it after the offsite, well before Christmas.",
+        Some(config.clone()),
+    );
+    let texts = found.iter().map(|r| r.text()).collect::<Vec<_>>();
+    assert_eq!(vec!["after the offsite", "before Christmas"], texts);
+    assert_eq!(14..31, found[0].span());
+    assert!(parse("sprint 43", Some(config)).is_err());
+}
+
+#[test]
+fn business_days() {
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (phrase, start, end) in [
+        (
+            "3 business days from now",
+            first_moment_of_day(1969, 5, 9),
+            first_moment_of_day(1969, 5, 10),
+        ),
+        (
+            "3 business days ago",
+            first_moment_of_day(1969, 5, 1),
+            first_moment_of_day(1969, 5, 2),
+        ),
+        (
+            "next business day",
+            first_moment_of_day(1969, 5, 7),
+            first_moment_of_day(1969, 5, 8),
+        ),
+        (
+            "last working day",
+            first_moment_of_day(1969, 5, 5),
+            first_moment_of_day(1969, 5, 6),
+        ),
+        (
+            "the business day after May 9",
+            first_moment_of_day(1969, 5, 12),
+            first_moment_of_day(1969, 5, 13),
+        ),
+        (
+            "2 workdays before May 12",
+            first_moment_of_day(1969, 5, 8),
+            first_moment_of_day(1969, 5, 9),
+        ),
+        (
+            "within 5 business days of May 6",
+            first_moment_of_day(1969, 5, 6),
+            first_moment_of_day(1969, 5, 14),
+        ),
+        (
+            "within 2 weeks of May 6",
+            first_moment_of_day(1969, 5, 6),
+            first_moment_of_day(1969, 5, 21),
+        ),
+    ] {
+        let (d1, d2, _) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(start, d1, "{}", phrase);
+        assert_eq!(end, d2, "{}", phrase);
+    }
+    let range = parse_range("3 business days from now", Some(config.clone())).unwrap();
+    assert_eq!(two_timer::Period::Day, range.period());
+    let range = parse_range("within 5 business days of May 6", Some(config.clone())).unwrap();
+    assert!(range.is_range());
+    // Memorial Day is May 26, 1969
+    let federal = config.days_off(Holidays::us_federal());
+    let (d1, _, _) = parse("3 business days after May 22", Some(federal.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1969, 5, 28), d1);
+    let (d1, _, _) = parse("3 weekdays after May 22", Some(federal.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1969, 5, 27), d1);
+    assert!(!federal.is_business_day(NaiveDate::from_ymd_opt(1969, 5, 26).unwrap()));
+    // New Year's Day 2022 was a Saturday and observed on December 31, 2021
+    assert!(!federal.is_business_day(NaiveDate::from_ymd_opt(2021, 12, 31).unwrap()));
+    let days = two_timer::business_days("weekdays this month", Some(config.clone())).unwrap();
+    assert_eq!(22, days.len());
+    let days = two_timer::business_days("business days this month", Some(federal)).unwrap();
+    assert_eq!(21, days.len());
+    assert_eq!(NaiveDate::from_ymd_opt(1969, 5, 30).unwrap(), days[20]);
+    let days = two_timer::business_days("next week", Some(config.clone())).unwrap();
+    assert_eq!(5, days.len());
+    assert_eq!(NaiveDate::from_ymd_opt(1969, 5, 12).unwrap(), days[0]);
+}
+
+#[test]
+fn work_week() {
+    let config = Config::new()
+        .now(precise_moment(1969, 5, 6, 12, 3, 5))
+        .work_week(&[
+            chrono::Weekday::Sun,
+            chrono::Weekday::Mon,
+            chrono::Weekday::Tue,
+            chrono::Weekday::Wed,
+            chrono::Weekday::Thu,
+        ]);
+    let (d1, _, _) = parse("the business day after May 8", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1969, 5, 11), d1);
+    let (d1, _, _) = parse("this weekday", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1969, 5, 6), d1);
+    assert!(!config.is_business_day(NaiveDate::from_ymd_opt(1969, 5, 9).unwrap()));
+    let recurrence = Recurrence::new("every weekday", Some(config)).unwrap();
+    assert_eq!(
+        &[
+            chrono::Weekday::Mon,
+            chrono::Weekday::Tue,
+            chrono::Weekday::Wed,
+            chrono::Weekday::Thu,
+            chrono::Weekday::Sun
+        ],
+        recurrence.weekdays()
+    );
+}
+
+#[test]
+fn open_ranges() {
+    let now = precise_moment(1969, 5, 6, 12, 3, 5);
+    let config = Config::new().now(now);
+    for (phrase, start, end) in [
+        (
+            "before May",
+            two_timer::first_moment(),
+            first_moment_of_day(1969, 5, 1),
+        ),
+        (
+            "before the end of May",
+            two_timer::first_moment(),
+            first_moment_of_day(1969, 6, 1),
+        ),
+        ("until Friday", now, first_moment_of_day(1969, 5, 9)),
+        ("by Friday", now, first_moment_of_day(1969, 5, 10)),
+        ("by noon", now, precise_moment(1969, 5, 7, 12, 0, 0)),
+        (
+            "until the end of the month",
+            now,
+            first_moment_of_day(1969, 6, 1),
+        ),
+        (
+            "any time after 2020",
+            first_moment_of_day(2021, 1, 1),
+            two_timer::last_moment(),
+        ),
+        (
+            "after 2020",
+            first_moment_of_day(2021, 1, 1),
+            two_timer::last_moment(),
+        ),
+        (
+            "after 1968",
+            first_moment_of_day(1969, 1, 1),
+            two_timer::last_moment(),
+        ),
+        (
+            "2020 onward",
+            first_moment_of_day(2020, 1, 1),
+            two_timer::last_moment(),
+        ),
+        (
+            "from May 10 on",
+            first_moment_of_day(1969, 5, 10),
+            two_timer::last_moment(),
+        ),
+        (
+            "no earlier than the end of May",
+            first_moment_of_day(1969, 6, 1),
+            two_timer::last_moment(),
+        ),
+    ] {
+        let (d1, d2, is_range) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(start, d1, "{}", phrase);
+        assert_eq!(end, d2, "{}", phrase);
+        assert!(!is_range, "{}", phrase);
+    }
+    let range = parse_range("before 2020", Some(config.clone())).unwrap();
+    assert!(range.is_unbounded_start());
+    assert!(!range.is_unbounded_end());
+    let range = parse_range("May 6 or later", Some(config.clone())).unwrap();
+    assert!(!range.is_unbounded_start());
+    assert!(range.is_unbounded_end());
+    let range = parse_range("since May", Some(config.clone())).unwrap();
+    assert!(!range.is_unbounded_start());
+    assert!(!range.is_unbounded_end());
+    match parse("until 1968", Some(config.clone())) {
+        Err(TimeError::Misordered(_)) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+    assert!(parse("before May onward", Some(config)).is_err());
+}
+
+#[test]
+fn rolling_windows() {
+    let now = precise_moment(1969, 5, 6, 12, 3, 5);
+    let config = Config::new().now(now);
+    let whole = config.whole_windows(true);
+    for (phrase, exact, whole_units) in [
+        (
+            "the last 7 days",
+            (precise_moment(1969, 4, 29, 12, 3, 5), now),
+            (first_moment_of_day(1969, 4, 29), first_moment_of_day(1969, 5, 6)),
+        ),
+        (
+            "past 24 hours",
+            (precise_moment(1969, 5, 5, 12, 3, 5), now),
+            (
+                precise_moment(1969, 5, 5, 12, 0, 0),
+                precise_moment(1969, 5, 6, 12, 0, 0),
+            ),
+        ),
+        (
+            "the next 3 months",
+            (now, precise_moment(1969, 8, 6, 12, 3, 5)),
+            (first_moment_of_day(1969, 6, 1), first_moment_of_day(1969, 9, 1)),
+        ),
+        (
+            "over the previous fortnight",
+            (precise_moment(1969, 4, 22, 12, 3, 5), now),
+            (first_moment_of_day(1969, 4, 21), first_moment_of_day(1969, 5, 5)),
+        ),
+        (
+            "in the next 2 business days",
+            (first_moment_of_day(1969, 5, 7), first_moment_of_day(1969, 5, 9)),
+            (first_moment_of_day(1969, 5, 7), first_moment_of_day(1969, 5, 9)),
+        ),
+    ] {
+        let (d1, d2, _) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(exact, (d1, d2), "{}", phrase);
+        let (d1, d2, _) = parse(phrase, Some(whole.clone())).unwrap();
+        assert_eq!(whole_units, (d1, d2), "{}", phrase);
+    }
+    // "last week" remains the calendar week
+    let (d1, d2, _) = parse("last week", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1969, 4, 28), d1);
+    assert_eq!(first_moment_of_day(1969, 5, 5), d2);
+    let range = parse_range("the past 24 hours", Some(config)).unwrap();
+    assert_eq!(two_timer::Period::Hour, range.period());
+}
+
+#[test]
+fn compound_days() {
+    // a Tuesday
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (phrase, day) in [
+        ("the day after tomorrow", (5, 8)),
+        ("the day before yesterday", (5, 4)),
+        ("Tuesday after next", (5, 20)),
+        ("the Monday before last", (4, 21)),
+        ("two weeks from Friday", (5, 23)),
+        ("a week from Tuesday", (5, 13)),
+        ("a week from today", (5, 13)),
+        ("3 days from May 6", (5, 9)),
+        ("next Friday week", (5, 23)),
+        ("Friday week", (5, 16)),
+    ] {
+        let range = parse_range(phrase, Some(config.clone())).unwrap();
+        assert_eq!(first_moment_of_day(1969, day.0, day.1), range.start(), "{}", phrase);
+        assert_eq!(
+            first_moment_of_day(1969, day.0, day.1) + Duration::days(1),
+            range.end(),
+            "{}",
+            phrase
+        );
+        assert_eq!(two_timer::Period::Day, range.period(), "{}", phrase);
+    }
+    let range = parse_range("a week from Tuesday at 3 PM", Some(config.clone())).unwrap();
+    assert_eq!(precise_moment(1969, 5, 13, 15, 0, 0), range.start());
+    assert_eq!(two_timer::Period::Second, range.period());
+    let range = parse_range("2 weeks from now", Some(config.clone())).unwrap();
+    assert_eq!(two_timer::Period::Week, range.period());
+    let (d1, d2, _) = parse("a week ago", Some(config)).unwrap();
+    assert_eq!(precise_moment(1969, 4, 29, 12, 3, 5), d1);
+    assert_eq!(precise_moment(1969, 5, 6, 12, 3, 5), d2);
+}
+
+#[test]
+fn nth_weekdays() {
+    // a Tuesday
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (phrase, day) in [
+        ("the first Monday of May", (1969, 5, 5)),
+        ("the last Friday of the month", (1969, 5, 30)),
+        ("the second Tuesday in March 1970", (1970, 3, 10)),
+        ("the 3rd Wed of next month", (1969, 6, 18)),
+        ("the last business day of the quarter", (1969, 6, 30)),
+        ("the first weekday of June 1969", (1969, 6, 2)),
+        ("the last day of February 1972", (1972, 2, 29)),
+        ("the 100th day of 1969", (1969, 4, 10)),
+    ] {
+        let (d1, d2, _) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(first_moment_of_day(day.0, day.1, day.2), d1, "{}", phrase);
+        assert_eq!(
+            first_moment_of_day(day.0, day.1, day.2) + Duration::days(1),
+            d2,
+            "{}",
+            phrase
+        );
+    }
+    // business days respect days off
+    let days_off = config.clone().days_off(Holidays::us_federal());
+    let (d1, _, _) = parse("the first business day of next year", Some(days_off)).unwrap();
+    assert_eq!(first_moment_of_day(1970, 1, 2), d1);
+    let (d1, d2, _) = parse(
+        "from the first Monday of May to the last Friday of June",
+        Some(config.clone()),
+    )
+    .unwrap();
+    assert_eq!(first_moment_of_day(1969, 5, 5), d1);
+    assert_eq!(first_moment_of_day(1969, 6, 27), d2);
+    match parse("the fifth Monday of May", Some(config.clone())) {
+        Err(TimeError::ImpossibleDate(_)) => (),
+        _ => panic!("there are only four Mondays in May 1969"),
+    }
+    let huge = "the 99999999999999999999th Monday of May";
+    assert!(parse(huge, Some(config.clone())).is_err());
+    let range = parse_range("the last Friday of the month", Some(config)).unwrap();
+    assert_eq!(two_timer::Period::Day, range.period());
+}
+
+#[test]
+fn period_parts() {
+    // a Tuesday
+    let config = Config::new().now(precise_moment(1969, 5, 6, 12, 3, 5));
+    for (phrase, start, end) in [
+        (
+            "the end of the month",
+            first_moment_of_day(1969, 5, 21),
+            first_moment_of_day(1969, 6, 1),
+        ),
+        (
+            "the start of next week",
+            first_moment_of_day(1969, 5, 12),
+            first_moment_of_day(1969, 5, 14),
+        ),
+        (
+            "mid-March",
+            first_moment_of_day(1969, 3, 11),
+            first_moment_of_day(1969, 3, 21),
+        ),
+        (
+            "early 1970",
+            first_moment_of_day(1970, 1, 1),
+            first_moment_of_day(1970, 5, 2),
+        ),
+        (
+            "late June",
+            first_moment_of_day(1969, 6, 21),
+            first_moment_of_day(1969, 7, 1),
+        ),
+        (
+            "the middle of Q3",
+            first_moment_of_day(1969, 7, 31),
+            first_moment_of_day(1969, 8, 31),
+        ),
+        (
+            "the end of yesterday",
+            precise_moment(1969, 5, 5, 16, 0, 0),
+            first_moment_of_day(1969, 5, 6),
+        ),
+        (
+            "from early May to late June",
+            first_moment_of_day(1969, 5, 1),
+            first_moment_of_day(1969, 6, 21),
+        ),
+        (
+            "from the end of May through the middle of June",
+            first_moment_of_day(1969, 5, 21),
+            first_moment_of_day(1969, 6, 21),
+        ),
+        (
+            "since the middle of April",
+            first_moment_of_day(1969, 4, 11),
+            precise_moment(1969, 5, 6, 12, 3, 5),
+        ),
+    ] {
+        let (d1, d2, _) = parse(phrase, Some(config.clone())).unwrap();
+        assert_eq!(start, d1, "{}", phrase);
+        assert_eq!(end, d2, "{}", phrase);
+    }
+    // "since the end of" still means since the period is over
+    let (d1, _, _) = parse("since the end of April", Some(config.clone())).unwrap();
+    assert_eq!(first_moment_of_day(1969, 5, 1), d1);
+    let range = parse_range("the end of the month", Some(config)).unwrap();
+    assert_eq!(two_timer::Period::Day, range.period());
+}
+
+#[test]
+fn two_timer_options() {
+    let now = ["--now", "May 6, 1969"];
+    let output = run(
+        env!("CARGO_BIN_EXE_two-timer"),
+        &[&now[..], &["yesterday"]].concat(),
+        "",
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        "1969-05-05 00:00:00 --- 1969-05-06 00:00:00\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    for (args, expected) in [
+        (
+            vec!["this week"],
+            "1969-05-05T00:00:00/1969-05-12T00:00:00\n",
+        ),
+        (
+            vec!["--week-starts-sunday", "this week"],
+            "1969-05-04T00:00:00/1969-05-11T00:00:00\n",
+        ),
+        (vec!["Friday"], "1969-05-02T00:00:00/1969-05-03T00:00:00\n"),
+        (
+            vec!["--future", "Friday"],
+            "1969-05-09T00:00:00/1969-05-10T00:00:00\n",
+        ),
+        (
+            vec![
+                "--pay-period-start",
+                "May 4, 1969",
+                "--pay-period-length",
+                "14",
+                "this pay period",
+            ],
+            "1969-05-04T00:00:00/1969-05-18T00:00:00\n",
+        ),
+    ] {
+        let args = [&now[..], &["--format", "iso"], &args[..]].concat();
+        let output = run(env!("CARGO_BIN_EXE_two-timer"), &args, "");
+        assert!(output.status.success(), "{:?}", args);
+        assert_eq!(
+            expected,
+            String::from_utf8_lossy(&output.stdout),
+            "{:?}",
+            args
+        );
+    }
+    let output = run(env!("CARGO_BIN_EXE_two-timer"), &["--help"], "");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("usage: two-timer"));
+}
+
+#[test]
+fn two_timer_formats() {
+    for (format, expected) in [
+        ("plain", "2019-01-01 00:00:00 --- 2019-01-02 00:00:00\n"),
+        ("iso", "2019-01-01T00:00:00/2019-01-02T00:00:00\n"),
+        (
+            "json",
+            "{\"end\":\"2019-01-02T00:00:00\",\"expression\":\"2019-01-01\",\"range\":false,\"start\":\"2019-01-01T00:00:00\"}\n",
+        ),
+    ] {
+        let output = run(
+            env!("CARGO_BIN_EXE_two-timer"),
+            &["--format", format, "2019-01-01"],
+            "",
+        );
+        assert!(output.status.success(), "{}", format);
+        assert_eq!(expected, String::from_utf8_lossy(&output.stdout), "{}", format);
+    }
+    // several words make up a single expression
+    let output = run(
+        env!("CARGO_BIN_EXE_two-timer"),
+        &["January", "1,", "2019"],
+        "",
+    );
+    assert_eq!(
+        "2019-01-01 00:00:00 --- 2019-01-02 00:00:00\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn two_timer_failures() {
+    // an unparsable line on STDIN is reported but the other lines are still handled
+    let output = run(
+        env!("CARGO_BIN_EXE_two-timer"),
+        &["--format", "iso"],
+        "2019-01-01\n\nblah blah\n2019-01-02\n",
+    );
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "2019-01-01T00:00:00/2019-01-02T00:00:00\n2019-01-02T00:00:00/2019-01-03T00:00:00\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("blah blah: "));
+    let output = run(env!("CARGO_BIN_EXE_two-timer"), &["blah", "blah"], "");
+    assert_eq!(Some(1), output.status.code());
+    assert!(output.stdout.is_empty());
+    // bad options are usage errors
+    for args in [
+        vec!["--format", "xml", "2019-01-01"],
+        vec!["--now"],
+        vec!["--now", "blah", "2019-01-01"],
+        vec!["--pay-period-length", "0", "2019-01-01"],
+        vec!["--bogus", "2019-01-01"],
+    ] {
+        let output = run(env!("CARGO_BIN_EXE_two-timer"), &args, "");
+        assert_eq!(Some(2), output.status.code(), "{:?}", args);
+        assert!(output.stdout.is_empty(), "{:?}", args);
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("usage: two-timer"),
+            "{:?}",
+            args
+        );
+    }
+}
+
+#[test]
+fn log_filter_tool() {
+    let log = "\
+2019-01-01 10:00:00 a
+  continued a
+2019-01-02 09:00:00 b
+  continued b
+2019-01-03 00:00:00 c
+2019-01-02 23:59:59 d
+";
+    let output = run(env!("CARGO_BIN_EXE_log-filter"), &["January 2, 2019"], log);
+    assert!(output.status.success());
+    assert_eq!(
+        "2019-01-02 09:00:00 b\n  continued b\n2019-01-02 23:59:59 d\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    // a sorted log stops at the first line past the range
+    let output = run(
+        env!("CARGO_BIN_EXE_log-filter"),
+        &["--sorted", "January 2, 2019"],
+        log,
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        "2019-01-02 09:00:00 b\n  continued b\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    let output = run(
+        env!("CARGO_BIN_EXE_log-filter"),
+        &["--pattern", r"\[(.*?)\]", "January 2, 2019"],
+        "x [Jan 1, 2019] a\ny [Jan 2, 2019] b\nz [Jan 3, 2019] c\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        "y [Jan 2, 2019] b\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    for args in [vec![], vec!["blah"], vec!["--pattern", "(", "2019"]] {
+        let output = run(env!("CARGO_BIN_EXE_log-filter"), &args, "");
+        assert_eq!(Some(2), output.status.code(), "{:?}", args);
+        assert!(output.stdout.is_empty(), "{:?}", args);
+    }
+}
+
+// run one of the command line tools, feeding it the given input
+fn run(program: &str, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
 fn first_moment_of_day(year: i32, month: u32, day: u32) -> NaiveDateTime {
     NaiveDate::from_ymd_opt(year, month, day)
         .unwrap()